@@ -0,0 +1,199 @@
+//! External bots: any program that speaks JSON Lines on stdin and stdout.
+//!
+//! Each game runs in a fresh process, started through the shell. Every line
+//! the game sends is one JSON object with a `type`:
+//!
+//! ```text
+//! {"type":"start","min":1,"max":100,"max_attempts":null,"max_lies":0}
+//! {"type":"feedback","guess":50,"outcome":"low"}       // or "high"
+//! {"type":"result","status":"won","attempts":7,"secret":37}
+//! ```
+//!
+//! After `start` and after each `feedback` the bot answers with one line,
+//! `{"guess":50}`, within the move timeout; a guess that ends the game gets
+//! `result` instead of `feedback`. Guesses outside the range, anything that
+//! isn't such a line, a timeout or an early exit forfeit the game. After
+//! `result` the bot's stdin is closed. Its stderr is passed through, so bots
+//! can log there.
+
+use std::fmt::{self, Display};
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::input::TimedReader;
+use crate::{Game, Number, Outcome, Status};
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A line from the game to the bot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Start { min: i128, max: i128, max_attempts: Option<u32>, max_lies: u32 },
+    Feedback { guess: i128, outcome: Outcome },
+    Result { status: Status, attempts: u32, secret: i128 },
+}
+
+/// A line from the bot to the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Move {
+    pub guess: i128,
+}
+
+/// Why a bot forfeited.
+#[derive(Debug)]
+pub enum BotError {
+    Spawn(io::Error),
+    Timeout(Duration),
+    /// The bot closed its output; the exit status if it had exited.
+    Exited(Option<ExitStatus>),
+    Protocol(String),
+    Io(io::Error),
+}
+
+impl Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BotError::Spawn(err) => write!(f, "could not start the bot: {err}"),
+            BotError::Timeout(limit) => write!(f, "no move within {}s", limit.as_secs_f64()),
+            BotError::Exited(Some(status)) => write!(f, "the bot exited ({status})"),
+            BotError::Exited(None) => write!(f, "the bot closed its output"),
+            BotError::Protocol(err) => write!(f, "protocol error: {err}"),
+            BotError::Io(err) => write!(f, "lost the bot: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for BotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BotError::Spawn(err) | BotError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A running bot process, good for one game. Killed when dropped.
+pub struct ExternalBot {
+    command: String,
+    child: Child,
+    stdin: Option<ChildStdin>,  // taken to close it after the result
+    stdout: TimedReader,
+    timeout: Duration,
+}
+
+impl ExternalBot {
+    /// Starts `command` through the shell, as typed.
+    pub fn spawn(command: &str) -> Result<Self, BotError> {
+        let mut child = shell(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(BotError::Spawn)?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = TimedReader::new(BufReader::new(child.stdout.take().expect("stdout is piped")));
+        Ok(ExternalBot { command: command.to_string(), child, stdin: Some(stdin), stdout, timeout: DEFAULT_TIMEOUT })
+    }
+
+    /// How long each move may take.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Sends `start`; call once before the first `next_guess`.
+    pub fn start<N: Number>(&mut self, game: &Game<N>) -> Result<(), BotError> {
+        let range = game.range();
+        self.send(&Message::Start {
+            min: range.min().to_i128(),
+            max: range.max().to_i128(),
+            max_attempts: game.max_attempts(),
+            max_lies: game.max_lies(),
+        })
+    }
+
+    /// Waits for the bot's next guess and checks it's in the game's range.
+    pub fn next_guess<N: Number>(&mut self, game: &Game<N>) -> Result<N, BotError> {
+        self.stdout.set_deadline(Some(Instant::now() + self.timeout));
+        let mut line = String::new();
+        match self.stdout.read_line(&mut line) {
+            Ok(0) => return Err(BotError::Exited(self.exit_status())),
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => return Err(BotError::Timeout(self.timeout)),
+            Err(err) => return Err(BotError::Io(err)),
+        }
+        let Move { guess } = serde_json::from_str(line.trim())
+            .map_err(|err| BotError::Protocol(format!("expected {{\"guess\": <number>}}, got `{}`: {err}", line.trim())))?;
+        N::from_i128(guess)
+            .filter(|&n| game.range().contains(n))
+            .ok_or_else(|| BotError::Protocol(format!("{guess} is outside {}", game.range())))
+    }
+
+    pub fn feedback<N: Number>(&mut self, guess: N, outcome: Outcome) -> Result<(), BotError> {
+        self.send(&Message::Feedback { guess: guess.to_i128(), outcome })
+    }
+
+    /// Sends `result` and closes the bot's input. A bot that already quit
+    /// is fine here.
+    pub fn finish<N: Number>(&mut self, game: &Game<N>) {
+        let result = Message::Result { status: game.status(), attempts: game.attempts(), secret: game.secret().to_i128() };
+        let _ = self.send(&result);
+        self.stdin = None;
+    }
+
+    /// A bot that stopped reading is only caught at the next read, which
+    /// can tell whether it exited or left a bad line behind.
+    fn send(&mut self, message: &Message) -> Result<(), BotError> {
+        let Some(stdin) = self.stdin.as_mut() else { return Ok(()) };
+        let line = serde_json::to_string(message).map_err(|err| BotError::Io(err.into()))?;
+        match writeln!(stdin, "{line}").and_then(|()| stdin.flush()) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {
+                self.stdin = None;
+                Ok(())
+            }
+            written => written.map_err(BotError::Io),
+        }
+    }
+
+    /// Gives a bot that closed its output a moment to exit.
+    fn exit_status(&mut self) -> Option<ExitStatus> {
+        for _ in 0..10 {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                return Some(status);
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        None
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        self.stdin = None;
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
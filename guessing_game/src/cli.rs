@@ -0,0 +1,343 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand};
+use clap_complete::Shell;
+use guessing_game::tournament::Entrant;
+use guessing_game::{feedback, strategy, word};
+use guessing_game::{Difficulty, GuessRange};
+
+/// Environment variable consulted when `--seed` is not given.
+pub const SEED_ENV: &str = "GUESSING_GAME_SEED";
+
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    play: PlayArgs,  // bare `guessing_game [options]` means `play`
+}
+
+impl Cli {
+    pub fn into_command(self) -> Command {
+        self.command.unwrap_or(Command::Play(self.play))
+    }
+}
+
+/// New modes plug in here: add a variant and handle it in `main`.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Guess the computer's number (the default)
+    Play(PlayArgs),
+    /// Show win/loss statistics and attempts-to-win histograms
+    Stats,
+    /// Take turns at one keyboard with 2-8 named players
+    Hotseat(HotseatArgs),
+    /// Run a round-robin or knockout tournament between players and bots
+    Tournament(TournamentArgs),
+    /// Host networked rooms over TCP
+    Serve(ServeArgs),
+    /// Join a server started with `serve`
+    Connect(ConnectArgs),
+    /// Serve the HTTP/JSON game API
+    Http {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+    },
+    /// Think of a number and let the computer guess it
+    Reverse(RangeArgs),
+    /// Benchmark automatic strategies over many seeded games
+    Simulate(SimulateArgs),
+    /// Crack a secret digit code from bulls and cows
+    Code(CodeArgs),
+    /// Guess a secret word from per-letter clues
+    Words(WordArgs),
+    /// Pick up a game saved with `save`, Ctrl-C or end of input
+    Resume(ResumeArgs),
+    /// Check a game log recorded with `--record` and play it back
+    Replay(ReplayArgs),
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct RangeArgs {
+    /// Range preset
+    #[arg(short, long, default_value = "normal", value_parser = difficulty_parser())]
+    pub difficulty: Difficulty,
+
+    /// Lowest possible secret (overrides the preset)
+    #[arg(long, allow_negative_numbers = true)]
+    pub min: Option<i128>,
+
+    /// Highest possible secret (overrides the preset)
+    #[arg(long, allow_negative_numbers = true)]
+    pub max: Option<i128>,
+}
+
+#[derive(Debug, Args)]
+pub struct GameArgs {
+    #[command(flatten)]
+    pub range: RangeArgs,
+
+    /// Seed for reproducible secrets
+    #[arg(long, env = SEED_ENV)]
+    pub seed: Option<u64>,
+
+    /// Lose if the secret is not found within this many guesses
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_attempts: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub game: GameArgs,
+
+    /// Stream the game to WebSocket spectators listening on this address
+    #[arg(long, value_name = "ADDR")]
+    pub spectate: Option<String>,
+
+    /// Use the line-by-line interface even on a terminal
+    #[arg(long)]
+    pub plain: bool,
+
+    /// Countdown: lose if the number is not found within this many seconds
+    #[arg(long, value_name = "SECS", value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    /// Time the game to the millisecond and keep personal bests
+    #[arg(long)]
+    pub speedrun: bool,
+
+    /// Ulam's game: low/high answers may be lies, up to this many times (low-high feedback only)
+    #[arg(long, default_value_t = 0, value_name = "K")]
+    pub lies: u32,
+
+    /// How misses are described (anything but low-high plays line by line)
+    #[arg(long, default_value = feedback::NAMES[0], value_parser = PossibleValuesParser::new(feedback::NAMES))]
+    pub feedback: String,
+
+    /// Resume the most recent unfinished game instead of starting one, with its own feedback
+    #[arg(long = "continue", conflicts_with_all = ["record", "feedback"])]
+    pub resume_latest: bool,
+
+    /// Log the game as JSON Lines for `replay`
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Let a program play instead, over the JSON Lines bot protocol
+    #[arg(long, value_name = "COMMAND", conflicts_with = "resume_latest")]
+    pub bot: Option<String>,
+
+    /// Seconds the bot may take per move
+    #[arg(long, value_name = "SECS", default_value = "5", value_parser = parse_seconds)]
+    pub bot_timeout: Duration,
+}
+
+#[derive(Debug, Args)]
+pub struct ResumeArgs {
+    /// Save file to load
+    pub file: PathBuf,
+
+    /// Use the line-by-line interface even on a terminal
+    #[arg(long)]
+    pub plain: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ReplayArgs {
+    /// Log written by `play --record`
+    pub file: PathBuf,
+
+    /// Wait for Enter before each move instead of keeping the original timing
+    #[arg(long)]
+    pub step: bool,
+
+    /// Play back this many times faster; 0 shows everything at once
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+    pub speed: f64,
+
+    /// Only check the log against the game engine
+    #[arg(long, conflicts_with_all = ["step", "speed"])]
+    pub check: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct HotseatArgs {
+    #[command(flatten)]
+    pub game: GameArgs,
+
+    /// Player name, in turn order (repeat for each player)
+    #[arg(short, long = "player", required = true)]
+    pub players: Vec<String>,
+
+    /// Give every player their own number; fewest attempts wins
+    #[arg(long)]
+    pub race: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TournamentArgs {
+    #[command(flatten)]
+    pub game: GameArgs,
+
+    /// Entrant, in seeding order: a name, `bot:<strategy>` or `cmd:<command>` (repeat for each)
+    #[arg(short, long = "player", required = true)]
+    pub players: Vec<Entrant>,
+
+    /// Single elimination instead of round-robin
+    #[arg(long)]
+    pub knockout: bool,
+
+    /// Seconds a `cmd:` entrant may take per move
+    #[arg(long, value_name = "SECS", default_value = "5", value_parser = parse_seconds)]
+    pub bot_timeout: Duration,
+}
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    #[command(flatten)]
+    pub range: RangeArgs,
+
+    /// Seed for reproducible secrets
+    #[arg(long, env = SEED_ENV)]
+    pub seed: Option<u64>,
+
+    /// Address to listen on
+    #[arg(long, default_value = "0.0.0.0:4000")]
+    pub bind: String,
+}
+
+#[derive(Debug, Args)]
+pub struct ConnectArgs {
+    /// Server address, e.g. 192.168.1.20:4000
+    pub addr: String,
+
+    /// Name shown to other players
+    #[arg(short, long)]
+    pub name: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    #[command(flatten)]
+    pub game: GameArgs,
+
+    /// Let the game lie up to this many times (try `--strategy tolerant`)
+    #[arg(long, default_value_t = 0, value_name = "K")]
+    pub lies: u32,
+
+    /// Games per strategy
+    #[arg(long, default_value_t = 1_000, value_parser = clap::value_parser!(u32).range(1..))]
+    pub games: u32,
+
+    /// Strategies to run (repeatable; all by default)
+    #[arg(long = "strategy", value_parser = PossibleValuesParser::new(strategy::NAMES))]
+    pub strategies: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct CodeArgs {
+    /// Digits in the code
+    #[arg(short, long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=10))]
+    pub length: u8,
+
+    /// Allow a digit to appear more than once
+    #[arg(long)]
+    pub repeats: bool,
+
+    /// Seed for reproducible codes
+    #[arg(long, env = SEED_ENV)]
+    pub seed: Option<u64>,
+
+    /// Lose if the code is not cracked within this many guesses
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_attempts: Option<u32>,
+
+    /// Watch the computer crack the code instead (up to 6 digits, 5 with repeats)
+    #[arg(long)]
+    pub auto: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct WordArgs {
+    /// Word list file, one word per line (default: the built-in English list)
+    #[arg(long, value_name = "FILE")]
+    pub words: Option<PathBuf>,
+
+    /// Letters per word
+    #[arg(short, long, default_value_t = 5, value_parser = clap::value_parser!(u8).range(2..=15))]
+    pub length: u8,
+
+    /// Every later guess must use the letters revealed so far
+    #[arg(long)]
+    pub hard: bool,
+
+    /// Seed for reproducible words
+    #[arg(long, env = SEED_ENV)]
+    pub seed: Option<u64>,
+
+    /// Guesses allowed
+    #[arg(long, default_value_t = word::DEFAULT_MAX_ATTEMPTS, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_attempts: u32,
+}
+
+/// The resolved range, in the narrowest type that can hold it.
+pub enum Bounds {
+    Signed(GuessRange<i64>),
+    Unsigned(GuessRange<u64>),
+}
+
+impl RangeArgs {
+    /// Applies `--min`/`--max` on top of the difficulty preset.
+    pub fn bounds(&self) -> Result<Bounds, String> {
+        let (preset_min, preset_max) = self.difficulty.bounds();
+        let min = self.min.unwrap_or(preset_min.into());
+        let max = self.max.unwrap_or(preset_max.into());
+        if min > max {
+            return Err(format!("--min {min} is greater than --max {max}"));
+        }
+        Bounds::new(min, max)
+    }
+}
+
+impl Bounds {
+    /// Picks `i64` when both ends fit, else `u64`.
+    pub fn new(min: i128, max: i128) -> Result<Self, String> {
+        if let (Ok(min), Ok(max)) = (i64::try_from(min), i64::try_from(max)) {
+            Ok(Bounds::Signed(GuessRange::new(min, max).map_err(|e| e.to_string())?))
+        } else if let (Ok(min), Ok(max)) = (u64::try_from(min), u64::try_from(max)) {
+            Ok(Bounds::Unsigned(GuessRange::new(min, max).map_err(|e| e.to_string())?))
+        } else {
+            Err(format!("range {min}..={max} does not fit in i64 or u64"))
+        }
+    }
+}
+
+fn difficulty_parser() -> impl TypedValueParser<Value = Difficulty> {
+    PossibleValuesParser::new(Difficulty::ALL.map(Difficulty::name))
+        .map(|name| name.parse::<Difficulty>().expect("possible values are difficulty names"))
+}
+
+/// A finite factor of 0 or more.
+fn parse_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed >= 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err("expected a speed of 0 or more".to_string()),
+    }
+}
+
+/// Positive seconds, fractions allowed: `30`, `2.5`.
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    match value.parse::<f64>() {
+        Ok(secs) if secs > 0.0 => Duration::try_from_secs_f64(secs).map_err(|err| err.to_string()),
+        _ => Err("expected a positive number of seconds".to_string()),
+    }
+}
@@ -0,0 +1,326 @@
+//! Bulls and Cows: crack a secret digit code. A bull is a right digit in the
+//! right place, a cow a right digit in the wrong place.
+
+use std::fmt;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::Status;
+
+/// Shape of the codes in a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    length: usize,
+    repeats: bool,  // may a digit appear more than once?
+}
+
+impl Default for Rules {
+    /// The classic game: four distinct digits.
+    fn default() -> Self {
+        Rules { length: 4, repeats: false }
+    }
+}
+
+impl Rules {
+    pub const MAX_LENGTH: usize = 10;
+
+    /// Fails unless `length` is 1..=10.
+    pub fn new(length: usize, repeats: bool) -> Result<Self, String> {
+        if !(1..=Self::MAX_LENGTH).contains(&length) {
+            return Err(format!("code length must be 1-{}, not {length}", Self::MAX_LENGTH));
+        }
+        Ok(Rules { length, repeats })
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    /// Parses a guess like `0425`; spaces between digits are allowed.
+    pub fn parse(&self, input: &str) -> Result<Code, CodeError> {
+        let input: String = input.split_whitespace().collect();
+        if input.is_empty() {
+            return Err(CodeError::Empty);
+        }
+        let mut digits = Vec::with_capacity(input.len());
+        for c in input.chars() {
+            let digit = c.to_digit(10).ok_or(CodeError::NotADigit(c))?;
+            if !self.repeats && digits.contains(&(digit as u8)) {
+                return Err(CodeError::Repeated(digit as u8));
+            }
+            digits.push(digit as u8);
+        }
+        if digits.len() != self.length {
+            return Err(CodeError::WrongLength { expected: self.length, found: digits.len() });
+        }
+        Ok(Code(digits))
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Code {
+        if self.repeats {
+            Code((0..self.length).map(|_| rng.gen_range(0..10)).collect())
+        } else {
+            let mut digits: Vec<u8> = (0..10).collect();
+            digits.shuffle(rng);
+            digits.truncate(self.length);
+            Code(digits)
+        }
+    }
+
+    /// Every code these rules allow, in increasing order.
+    pub fn all_codes(&self) -> Vec<Code> {
+        let mut codes: Vec<Vec<u8>> = vec![Vec::new()];
+        for _ in 0..self.length {
+            let mut longer = Vec::new();
+            for code in &codes {
+                for digit in (0..10).filter(|d| self.repeats || !code.contains(d)) {
+                    longer.push([code.as_slice(), &[digit]].concat());
+                }
+            }
+            codes = longer;
+        }
+        codes.into_iter().map(Code).collect()
+    }
+}
+
+impl fmt::Display for Rules {
+    /// `4 distinct digits`, `5 digits, repeats allowed`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.repeats {
+            write!(f, "{} digits, repeats allowed", self.length)
+        } else {
+            write!(f, "{} distinct digits", self.length)
+        }
+    }
+}
+
+/// A code or a guess at one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Code(Vec<u8>);
+
+impl Code {
+    pub fn digits(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Bulls and cows for `guess` against this code.
+    pub fn score(&self, guess: &Code) -> Score {
+        let bulls = self.0.iter().zip(&guess.0).filter(|(a, b)| a == b).count();
+        let (mut mine, mut theirs) = ([0u8; 10], [0u8; 10]);
+        self.0.iter().for_each(|&d| mine[usize::from(d)] += 1);
+        guess.0.iter().for_each(|&d| theirs[usize::from(d)] += 1);
+        let common: usize = mine.iter().zip(&theirs).map(|(&a, &b)| usize::from(a.min(b))).sum();
+        Score { bulls: bulls as u8, cows: (common - bulls) as u8 }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|digit| write!(f, "{digit}"))
+    }
+}
+
+/// Feedback for one guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub bulls: u8,
+    pub cows: u8,
+}
+
+impl fmt::Display for Score {
+    /// `1 bull, 2 cows`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: u8| if n == 1 { "" } else { "s" };
+        write!(f, "{} bull{}, {} cow{}", self.bulls, plural(self.bulls), self.cows, plural(self.cows))
+    }
+}
+
+/// Why a guess could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    Empty,
+    NotADigit(char),
+    Repeated(u8),
+    WrongLength { expected: usize, found: usize },
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::Empty => write!(f, "no guess entered"),
+            CodeError::NotADigit(c) => write!(f, "`{c}` is not a digit"),
+            CodeError::Repeated(digit) => write!(f, "{digit} appears twice but digits can't repeat"),
+            CodeError::WrongLength { expected, found } => write!(f, "expected {expected} digits, got {found}"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+/// One round of Bulls and Cows; the digit-code counterpart of `Game`.
+#[derive(Debug, Clone)]
+pub struct CodeGame {
+    rules: Rules,
+    secret: Code,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    status: Status,
+    history: Vec<(Code, Score)>,
+}
+
+impl CodeGame {
+    pub fn new<R: Rng + ?Sized>(rules: Rules, rng: &mut R) -> Self {
+        let secret = rules.sample(rng);
+        Self::with_secret(rules, secret)
+    }
+
+    /// Panics if `secret` breaks the rules.
+    pub fn with_secret(rules: Rules, secret: Code) -> Self {
+        assert_eq!(rules.parse(&secret.to_string()).as_ref(), Ok(&secret), "secret {secret} breaks the rules");
+        CodeGame { rules, secret, attempts: 0, max_attempts: None, status: Status::Playing, history: Vec::new() }
+    }
+
+    /// Limits the number of guesses; missing on the last one loses the game.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts.map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn history(&self) -> &[(Code, Score)] {
+        &self.history
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    /// Scores as-is; frontends validate input with `Rules::parse` first.
+    /// Guesses made after the game is over are answered but not counted.
+    pub fn guess(&mut self, guess: &Code) -> Score {
+        let score = self.secret.score(guess);
+        if self.is_over() {
+            return score;
+        }
+
+        self.attempts += 1;
+        self.history.push((guess.clone(), score));
+        if usize::from(score.bulls) == self.rules.length {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        score
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_over() {
+            self.status = Status::GaveUp;
+        }
+    }
+}
+
+/// Knuth's minimax solver: guesses the code whose worst-case answer leaves
+/// the fewest candidates, preferring codes that could still be the secret.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    rules: Rules,
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+    attempts: u32,
+}
+
+impl Solver {
+    /// Most guess-candidate pairs scored per move. Past it only candidates
+    /// are considered as guesses, and past it again an evenly spaced sample
+    /// of them; scoring every code against every candidate would be too slow.
+    const SEARCH_BUDGET: usize = 1 << 20;
+    /// Longest codes the solver takes on; it lists every code up front, and
+    /// one more digit makes that ten times the work.
+    pub const MAX_LENGTH: usize = 6;
+    pub const MAX_LENGTH_WITH_REPEATS: usize = 5;
+
+    /// Fails for codes longer than `MAX_LENGTH`, or `MAX_LENGTH_WITH_REPEATS`.
+    pub fn new(rules: Rules) -> Result<Self, String> {
+        let max = if rules.repeats { Self::MAX_LENGTH_WITH_REPEATS } else { Self::MAX_LENGTH };
+        if rules.length > max {
+            return Err(format!(
+                "the solver cracks codes of up to {} distinct digits or {} with repeats, not {rules}",
+                Self::MAX_LENGTH, Self::MAX_LENGTH_WITH_REPEATS
+            ));
+        }
+        let all = rules.all_codes();
+        Ok(Solver { rules, candidates: all.clone(), all, attempts: 0 })
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Codes consistent with every answer so far.
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    /// `None` once the answers contradict each other.
+    pub fn next_guess(&self) -> Option<Code> {
+        if self.candidates.len() <= 2 {
+            return self.candidates.first().cloned();
+        }
+        if self.attempts == 0 {
+            return Some(self.opening());
+        }
+        let n = self.candidates.len();
+        let pool = if self.all.len() * n <= Self::SEARCH_BUDGET { &self.all } else { &self.candidates };
+        pool.iter()
+            .step_by((pool.len() * n).div_ceil(Self::SEARCH_BUDGET))
+            .min_by_key(|guess| (self.worst_case(guess), self.candidates.binary_search(guess).is_err()))
+            .cloned()
+    }
+
+    pub fn feedback(&mut self, guess: &Code, score: Score) {
+        self.attempts += 1;
+        self.candidates.retain(|code| code.score(guess) == score);
+    }
+
+    /// Knuth's `1122` generalised: pairs of digits, or the lowest distinct ones.
+    fn opening(&self) -> Code {
+        if self.rules.repeats {
+            Code((0..self.rules.length).map(|i| (i / 2 + 1) as u8).collect())
+        } else {
+            Code((0..self.rules.length as u8).collect())
+        }
+    }
+
+    /// Size of the largest group of candidates that would get the same score.
+    fn worst_case(&self, guess: &Code) -> usize {
+        let mut groups = [0usize; (Rules::MAX_LENGTH + 1) * (Rules::MAX_LENGTH + 1)];
+        for code in &self.candidates {
+            let score = code.score(guess);
+            groups[usize::from(score.bulls) * (Rules::MAX_LENGTH + 1) + usize::from(score.cows)] += 1;
+        }
+        groups.into_iter().max().unwrap_or(0)
+    }
+}
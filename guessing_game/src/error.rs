@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io;
+use crate::range::{GuessRange, Number};
+
+/// Why a line of input could not be used as a guess.
+#[derive(Debug)]
+pub enum GuessError<N = u32> {
+    Empty,
+    NotANumber(String),
+    OutOfRange { guess: N, range: GuessRange<N> },
+    Io(io::Error),
+}
+
+impl<N: Number> Display for GuessError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "no guess entered"),
+            GuessError::NotANumber(input) => write!(f, "`{input}` is not a number"),
+            GuessError::OutOfRange { guess, range } => write!(f, "{guess} is outside {range}"),
+            GuessError::Io(err) => write!(f, "failed to read guess: {err}"),
+        }
+    }
+}
+
+impl<N: Number> Error for GuessError<N> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GuessError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<N> From<io::Error> for GuessError<N> {
+    fn from(err: io::Error) -> Self {
+        GuessError::Io(err)
+    }
+}
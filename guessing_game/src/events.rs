@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use crate::hint::{Clue, Hint};
+use crate::{Game, Number, Outcome, Status};
+
+/// Something that happened in a game, for spectators and logs. Numbers are
+/// widened to `i128` so one event type covers every `Number`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent {
+    Created { min: i128, max: i128, max_attempts: Option<u32> },
+    Guess { guess: i128, attempt: u32 },
+    Feedback { guess: i128, outcome: Outcome },
+    Hint { hint: Hint, clue: String, penalty: u32 },
+    Won { attempts: u32, score: u32, secret: i128 },
+    Lost { attempts: u32, secret: i128, reason: LossReason },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LossReason {
+    OutOfAttempts,
+    OutOfTime,
+    GaveUp,
+}
+
+impl GameEvent {
+    pub fn created<N: Number>(game: &Game<N>) -> Self {
+        let range = game.range();
+        GameEvent::Created { min: range.min().to_i128(), max: range.max().to_i128(), max_attempts: game.max_attempts() }
+    }
+
+    /// The `Guess` and `Feedback` pair for the game's latest counted guess.
+    pub fn last_guess<N: Number>(game: &Game<N>) -> Option<[Self; 2]> {
+        let &(guess, outcome) = game.history().last()?;
+        let guess = guess.to_i128();
+        Some([GameEvent::Guess { guess, attempt: game.attempts() }, GameEvent::Feedback { guess, outcome }])
+    }
+
+    /// A hint the game just charged for; `penalty` is the total so far.
+    pub fn hint<N: Number>(game: &Game<N>, hint: Hint, clue: &Clue<N>) -> Self {
+        GameEvent::Hint { hint, clue: clue.to_string(), penalty: game.penalty() }
+    }
+
+    /// `Won` or `Lost` once the game is over.
+    pub fn finished<N: Number>(game: &Game<N>) -> Option<Self> {
+        let (attempts, secret) = (game.attempts(), game.secret().to_i128());
+        match game.status() {
+            Status::Playing => None,
+            Status::Won => Some(GameEvent::Won { attempts, score: game.score(), secret }),
+            Status::Lost => Some(GameEvent::Lost { attempts, secret, reason: LossReason::OutOfAttempts }),
+            Status::OutOfTime => Some(GameEvent::Lost { attempts, secret, reason: LossReason::OutOfTime }),
+            Status::GaveUp => Some(GameEvent::Lost { attempts, secret, reason: LossReason::GaveUp }),
+        }
+    }
+}
+
+/// Receives events as a session plays.
+pub type Observer = Box<dyn FnMut(&GameEvent) + Send>;
+
+pub(crate) fn notify(observers: &mut [Observer], event: GameEvent) {
+    observers.iter_mut().for_each(|observer| observer(&event));
+}
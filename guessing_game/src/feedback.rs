@@ -0,0 +1,100 @@
+use crate::Outcome;
+
+/// How a frontend tells the player about a missed guess. Numbers are `i128`
+/// so one implementation covers every `Number` type, as with `Strategy`.
+pub trait Feedback: Send {
+    fn name(&self) -> &'static str;
+
+    /// Called before each game with the inclusive range.
+    fn start(&mut self, _min: i128, _max: i128) {}
+
+    /// The line shown after a guess that missed `secret`; wins are announced
+    /// by the frontend. `outcome` is the game's answer, which a lying oracle
+    /// may have flipped.
+    fn miss(&mut self, guess: i128, secret: i128, outcome: Outcome) -> String;
+}
+
+/// Names accepted by `by_name`; the first is the default.
+pub const NAMES: [&str; 3] = ["low-high", "warmer-colder", "distance"];
+
+pub fn by_name(name: &str) -> Option<Box<dyn Feedback>> {
+    let feedback: Box<dyn Feedback> = match name {
+        "low-high" => Box::new(LowHigh),
+        "warmer-colder" => Box::new(WarmerColder::default()),
+        "distance" => Box::new(Distance::default()),
+        _ => return None,
+    };
+    Some(feedback)
+}
+
+/// The classic: too low or too high.
+#[derive(Debug, Default)]
+pub struct LowHigh;
+
+impl Feedback for LowHigh {
+    fn name(&self) -> &'static str {
+        "low-high"
+    }
+
+    fn miss(&mut self, _guess: i128, _secret: i128, outcome: Outcome) -> String {
+        if outcome == Outcome::Low { "Too low!" } else { "Too high!" }.to_string()
+    }
+}
+
+/// Compares each guess's distance with the previous one's.
+#[derive(Debug, Default)]
+pub struct WarmerColder {
+    last_distance: Option<u128>,
+}
+
+impl Feedback for WarmerColder {
+    fn name(&self) -> &'static str {
+        "warmer-colder"
+    }
+
+    fn start(&mut self, _min: i128, _max: i128) {
+        self.last_distance = None;
+    }
+
+    fn miss(&mut self, guess: i128, secret: i128, _outcome: Outcome) -> String {
+        let distance = guess.abs_diff(secret);
+        let line = match self.last_distance {
+            None => "Missed! Guess again to find out if you're getting warmer.",
+            Some(last) if distance < last => "Warmer!",
+            Some(last) if distance > last => "Colder!",
+            Some(_) => "Same distance!",
+        };
+        self.last_distance = Some(distance);
+        line.to_string()
+    }
+}
+
+/// Buckets the distance as a share of the range: burning down to freezing.
+#[derive(Debug, Default)]
+pub struct Distance {
+    size: u128,
+}
+
+impl Distance {
+    /// Upper bounds as a fraction of the range size; anything further is freezing.
+    const BUCKETS: [(f64, &'static str); 4] = [(0.02, "Burning!"), (0.05, "Hot!"), (0.10, "Warm."), (0.25, "Cold.")];
+}
+
+impl Feedback for Distance {
+    fn name(&self) -> &'static str {
+        "distance"
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.size = max.abs_diff(min) + 1;
+    }
+
+    fn miss(&mut self, guess: i128, secret: i128, _outcome: Outcome) -> String {
+        let share = guess.abs_diff(secret) as f64 / self.size.max(1) as f64;
+        Distance::BUCKETS
+            .iter()
+            .find(|&&(limit, _)| share <= limit)
+            .map_or("Freezing!", |&(_, line)| line)
+            .to_string()
+    }
+}
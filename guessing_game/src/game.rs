@@ -0,0 +1,379 @@
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::hint::{Clue, Hint, HintError};
+use crate::range::{GuessRange, Number};
+use crate::rng::{self, SeededRng};
+use crate::score;
+
+/// How likely the oracle is to lie about a miss while it still may.
+const LIE_CHANCE: f64 = 0.25;
+
+/// Result of comparing a guess against the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Low,
+    High,
+    Win,
+}
+
+impl Outcome {
+    /// The opposite answer to a miss; a win stays a win.
+    pub fn flipped(self) -> Self {
+        match self {
+            Outcome::Low => Outcome::High,
+            Outcome::High => Outcome::Low,
+            Outcome::Win => Outcome::Win,
+        }
+    }
+
+    /// `too low`, `too high` or `right`, as transcripts and logs put it.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Outcome::Low => "too low",
+            Outcome::High => "too high",
+            Outcome::Win => "right",
+        }
+    }
+}
+
+impl From<Ordering> for Outcome {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Less => Outcome::Low,
+            Ordering::Greater => Outcome::High,
+            Ordering::Equal => Outcome::Win,
+        }
+    }
+}
+
+/// Where a game stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Playing,
+    Won,
+    Lost,  // ran out of attempts
+    OutOfTime,
+    GaveUp,
+}
+
+/// A single round of the guessing game: one secret, any number of guesses.
+#[derive(Debug, Clone)]
+pub struct Game<N = u32> {
+    range: GuessRange<N>,
+    secret: N,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    status: Status,
+    history: Vec<(N, Outcome)>,
+    hints: Vec<(Hint, Clue<N>)>,
+    hinted_after: Vec<u32>,  // attempts made before each hint
+    penalty: u32,  // extra attempts charged for hints
+    liar: Option<Liar>,
+    clock: Clock,
+}
+
+/// Lying-oracle state (Ulam's game): which answers were lies, and the
+/// generator that decides when to lie.
+#[derive(Debug, Clone)]
+struct Liar {
+    max_lies: u32,
+    seed: u64,
+    lies: Vec<usize>,  // indices into `history`
+    rng: SeededRng,
+}
+
+/// Wall-clock timing; starts when a frontend first prompts, stops when the
+/// game ends.
+#[derive(Debug, Clone, Default)]
+struct Clock {
+    started: Option<Instant>,
+    carried: Duration,  // played before a resume
+    stopped: Option<Duration>,
+    time_limit: Option<Duration>,
+    guess_times: Vec<Duration>,  // elapsed at each counted guess
+}
+
+impl Game {
+    /// Starts a game with a random secret in 1..=100.
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Draws the secret from `rng`; pass a seeded generator to replay a game.
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::in_range(GuessRange::default(), rng)
+    }
+
+    /// Starts a game with a known secret (handy for tests and custom frontends).
+    pub fn with_secret(secret: u32) -> Self {
+        Self::with_secret_in(GuessRange::default(), secret)
+    }
+}
+
+impl<N: Number> Game<N> {
+    pub fn in_range<R: Rng + ?Sized>(range: GuessRange<N>, rng: &mut R) -> Self {
+        let secret = range.sample(rng);
+        Self::with_secret_in(range, secret)
+    }
+
+    /// Panics if `secret` lies outside `range`.
+    pub fn with_secret_in(range: GuessRange<N>, secret: N) -> Self {
+        assert!(range.contains(secret), "secret {secret} is outside {range}");
+        Game {
+            range,
+            secret,
+            attempts: 0,
+            max_attempts: None,
+            status: Status::Playing,
+            history: Vec::new(),
+            hints: Vec::new(),
+            hinted_after: Vec::new(),
+            penalty: 0,
+            liar: None,
+            clock: Clock::default(),
+        }
+    }
+
+    /// Limits the number of guesses; missing on the last one loses the game.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Ulam's game: up to `max_lies` low/high answers may be wrong. Which
+    /// ones is decided by a generator seeded with `seed`. A correct guess is
+    /// always answered truthfully.
+    pub fn with_lies(mut self, max_lies: u32, seed: u64) -> Self {
+        self.liar = (max_lies > 0).then(|| Liar { max_lies, seed, lies: Vec::new(), rng: rng::seeded(seed) });
+        self
+    }
+
+    /// Carries over time already played, e.g. by a saved game.
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.clock.carried = elapsed;
+        self
+    }
+
+    /// Countdown: the game is lost once this much time has passed on the clock.
+    pub fn with_time_limit(mut self, time_limit: Option<Duration>) -> Self {
+        self.clock.time_limit = time_limit;
+        self
+    }
+
+    pub fn range(&self) -> GuessRange<N> {
+        self.range
+    }
+
+    pub fn secret(&self) -> N {
+        self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts.map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// Counted guesses in order, with the feedback each got (lies included).
+    pub fn history(&self) -> &[(N, Outcome)] {
+        &self.history
+    }
+
+    pub fn max_lies(&self) -> u32 {
+        self.liar.as_ref().map_or(0, |liar| liar.max_lies)
+    }
+
+    /// Indices into `history` of the answers that were lies. Frontends should
+    /// only reveal these once the game is over.
+    pub fn lies(&self) -> &[usize] {
+        self.liar.as_ref().map_or(&[], |liar| &liar.lies)
+    }
+
+    /// The seed passed to `with_lies`, if the oracle may lie.
+    pub fn lie_seed(&self) -> Option<u64> {
+        self.liar.as_ref().map(|liar| liar.seed)
+    }
+
+    /// Hints given so far, in order.
+    pub fn hints(&self) -> &[(Hint, Clue<N>)] {
+        &self.hints
+    }
+
+    /// Each charged hint with the number of guesses made before it, so a
+    /// game can be rebuilt move by move.
+    pub fn hint_log(&self) -> impl Iterator<Item = (u32, Hint)> + '_ {
+        self.hinted_after.iter().zip(&self.hints).map(|(&after, &(hint, _))| (after, hint))
+    }
+
+    /// Extra attempts charged for hints.
+    pub fn penalty(&self) -> u32 {
+        self.penalty
+    }
+
+    /// Attempts plus the hint penalty; this is what the score is based on.
+    pub fn total_attempts(&self) -> u32 {
+        self.attempts + self.penalty
+    }
+
+    /// Gives a clue about the secret and charges the hint's penalty, along
+    /// with whether it was charged: asking again for a clue already given is
+    /// free and doesn't add to `hints`.
+    pub fn hint(&mut self, hint: Hint) -> Result<(Clue<N>, bool), HintError> {
+        if self.is_over() {
+            return Err(HintError::GameOver);
+        }
+        let last_guess = self.history.last().map(|&(guess, _)| guess);
+        let clue = Clue::new(hint, self.secret, self.candidates(), last_guess)?;
+        let new = !self.hints.iter().any(|(_, given)| *given == clue);
+        if new {
+            self.penalty += hint.penalty();
+            self.hints.push((hint, clue.clone()));
+            self.hinted_after.push(self.attempts);
+        }
+        Ok((clue, new))
+    }
+
+    /// Numbers still consistent with the feedback and interval hints so far.
+    /// Lies are corrected, so with a lying oracle this knows more than the
+    /// player does.
+    pub fn candidates(&self) -> GuessRange<N> {
+        let (mut lo, mut hi) = (self.range.min().to_i128(), self.range.max().to_i128());
+        for (i, &(guess, outcome)) in self.history.iter().enumerate() {
+            let outcome = if self.lies().contains(&i) { outcome.flipped() } else { outcome };
+            let guess = guess.to_i128();
+            match outcome {
+                Outcome::Low => lo = lo.max(guess + 1),
+                Outcome::High => hi = hi.min(guess - 1),
+                Outcome::Win => (lo, hi) = (guess, guess),
+            }
+        }
+        for (_, clue) in &self.hints {
+            if let Clue::Interval(interval) = clue {
+                lo = lo.max(interval.min().to_i128());
+                hi = hi.min(interval.max().to_i128());
+            }
+        }
+        let narrow = |n| N::from_i128(n).expect("candidates lie inside the range");
+        GuessRange::new(narrow(lo), narrow(hi)).expect("the secret is always a candidate")
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    /// Starts the clock if it is not running yet. Frontends call this when
+    /// they first prompt; without it the game is untimed.
+    pub fn start_clock(&mut self) {
+        self.clock.started.get_or_insert_with(Instant::now);
+    }
+
+    /// Time on the clock; frozen once the game is over.
+    pub fn elapsed(&self) -> Duration {
+        match (self.clock.stopped, self.clock.started) {
+            (Some(stopped), _) => stopped,
+            (None, Some(started)) => self.clock.carried + started.elapsed(),
+            (None, None) => self.clock.carried,
+        }
+    }
+
+    /// Whether the game has ever been on the clock.
+    pub fn is_timed(&self) -> bool {
+        self.clock.started.is_some() || !self.clock.carried.is_zero()
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.clock.time_limit
+    }
+
+    pub fn time_left(&self) -> Option<Duration> {
+        self.clock.time_limit.map(|limit| limit.saturating_sub(self.elapsed()))
+    }
+
+    /// When the countdown runs out, if the clock is running.
+    pub fn deadline(&self) -> Option<Instant> {
+        Some(self.clock.started? + self.clock.time_limit?.saturating_sub(self.clock.carried))
+    }
+
+    /// Clock time at each counted guess, parallel to `history`.
+    pub fn guess_times(&self) -> &[Duration] {
+        &self.clock.guess_times
+    }
+
+    /// Ends the game if the countdown has run out.
+    pub fn check_time(&mut self) {
+        if !self.is_over() && self.clock.started.is_some() && self.time_left() == Some(Duration::ZERO) {
+            self.finish(Status::OutOfTime);
+        }
+    }
+
+    /// Compares as-is; frontends validate input with `GuessRange::parse` first.
+    /// Guesses made after the game is over (including after the countdown
+    /// ran out) are answered but not counted. With `with_lies`, the answer to
+    /// a miss may be a lie.
+    pub fn guess(&mut self, guess: N) -> Outcome {
+        let mut outcome: Outcome = guess.cmp(&self.secret).into();
+        self.check_time();
+        if self.is_over() {
+            return outcome;
+        }
+        if let Some(liar) = &mut self.liar
+            && outcome != Outcome::Win
+            && (liar.lies.len() as u32) < liar.max_lies
+            && liar.rng.gen_bool(LIE_CHANCE)
+        {
+            liar.lies.push(self.history.len());
+            outcome = outcome.flipped();
+        }
+
+        self.attempts += 1;
+        self.history.push((guess, outcome));
+        self.clock.guess_times.push(self.elapsed());
+        if outcome == Outcome::Win {
+            self.finish(Status::Won);
+        } else if self.attempts_left() == Some(0) {
+            self.finish(Status::Lost);
+        }
+        outcome
+    }
+
+    /// Ends an unfinished game, e.g. when the player closes the input.
+    pub fn give_up(&mut self) {
+        if !self.is_over() {
+            self.finish(Status::GaveUp);
+        }
+    }
+
+    fn finish(&mut self, status: Status) {
+        self.clock.stopped = self.clock.started.map(|_| self.elapsed());
+        self.status = status;
+    }
+
+    /// Score for this game so far; only wins earn points, and hints count
+    /// against them as extra attempts.
+    pub fn score(&self) -> u32 {
+        match self.status {
+            Status::Won => score::score(self.range.size(), self.total_attempts()),
+            _ => 0,
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::{GuessRange, Number};
+
+/// Kinds of help a player can ask for. Each clue is worked out from the
+/// secret by `Game::hint` and costs `penalty` extra attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hint {
+    Parity,
+    Divisors,
+    DigitSum,
+    Within10,  // is the last guess within 10 of the secret?
+    Interval,  // halves the candidates around the secret
+}
+
+impl Hint {
+    pub const ALL: [Hint; 5] = [Hint::Parity, Hint::Divisors, Hint::DigitSum, Hint::Within10, Hint::Interval];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Hint::Parity => "parity",
+            Hint::Divisors => "divisors",
+            Hint::DigitSum => "digit-sum",
+            Hint::Within10 => "within-10",
+            Hint::Interval => "interval",
+        }
+    }
+
+    /// Attempts added to the final count.
+    pub fn penalty(self) -> u32 {
+        match self {
+            Hint::Parity | Hint::Within10 => 1,
+            Hint::Divisors | Hint::DigitSum => 2,
+            Hint::Interval => 3,
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Hint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Hint::ALL
+            .into_iter()
+            .find(|hint| hint.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown hint `{}`", s.trim()))
+    }
+}
+
+/// Parses a `hint <name>` command typed at the guess prompt; `None` if the
+/// line is something else. A bare or unknown hint comes back as the usage.
+pub fn parse_request(line: &str) -> Option<Result<Hint, String>> {
+    let mut words = line.split_whitespace();
+    if !words.next()?.eq_ignore_ascii_case("hint") {
+        return None;
+    }
+    let usage = |prefix: String| {
+        let hints: Vec<String> = Hint::ALL.iter().map(|hint| format!("{hint} (+{})", hint.penalty())).collect();
+        format!("{prefix}hints cost extra attempts: {}; type `hint <name>`", hints.join(", "))
+    };
+    Some(match words.next() {
+        None => Err(usage(String::new())),
+        Some(name) => name.parse().map_err(|err| usage(format!("{err}; "))),
+    })
+}
+
+/// What a hint revealed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Clue<N> {
+    Parity { even: bool },
+    Divisors(Vec<u32>),  // which of 2..=9 divide the secret
+    DigitSum(u32),
+    Within10 { guess: N, within: bool },
+    Interval(GuessRange<N>),
+}
+
+impl<N: Number> Clue<N> {
+    /// Works out the clue for `hint`. `candidates` is what the feedback so
+    /// far allows and `last_guess` the latest counted guess.
+    pub(crate) fn new(hint: Hint, secret: N, candidates: GuessRange<N>, last_guess: Option<N>) -> Result<Self, HintError> {
+        let value = secret.to_i128();
+        Ok(match hint {
+            Hint::Parity => Clue::Parity { even: value % 2 == 0 },
+            Hint::Divisors => Clue::Divisors((2..=9).filter(|&d| value % i128::from(d) == 0).collect()),
+            Hint::DigitSum => {
+                let digits = value.unsigned_abs().to_string();
+                Clue::DigitSum(digits.bytes().map(|digit| u32::from(digit - b'0')).sum())
+            }
+            Hint::Within10 => {
+                let guess = last_guess.ok_or(HintError::NoGuessYet)?;
+                Clue::Within10 { guess, within: (guess.to_i128() - value).abs() <= 10 }
+            }
+            Hint::Interval => {
+                let (lo, hi) = (candidates.min().to_i128(), candidates.max().to_i128());
+                let width = (hi - lo + 2) / 2;  // half the candidates, rounded up
+                let start = (lo + (value - lo) / width * width).min(hi - width + 1);
+                let narrow = |n| N::from_i128(n).expect("the interval lies inside the candidates");
+                Clue::Interval(GuessRange::new(narrow(start), narrow(start + width - 1)).expect("width is at least 1"))
+            }
+        })
+    }
+}
+
+impl<N: Number> fmt::Display for Clue<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Clue::Parity { even: true } => write!(f, "the number is even"),
+            Clue::Parity { even: false } => write!(f, "the number is odd"),
+            Clue::Divisors(divisors) if divisors.is_empty() => write!(f, "the number is divisible by none of 2-9"),
+            Clue::Divisors(divisors) => {
+                let divisors: Vec<String> = divisors.iter().map(u32::to_string).collect();
+                write!(f, "the number is divisible by {}", divisors.join(", "))
+            }
+            Clue::DigitSum(sum) => write!(f, "the digits of the number add up to {sum}"),
+            Clue::Within10 { guess, within: true } => write!(f, "{guess} is within 10 of the number"),
+            Clue::Within10 { guess, within: false } => write!(f, "{guess} is more than 10 away from the number"),
+            Clue::Interval(range) => write!(f, "the number is in {range}"),
+        }
+    }
+}
+
+/// Why a hint could not be given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintError {
+    GameOver,
+    NoGuessYet,
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HintError::GameOver => write!(f, "the game is over"),
+            HintError::NoGuessYet => write!(f, "make a guess first"),
+        }
+    }
+}
+
+impl std::error::Error for HintError {}
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use rand::Rng;
+use crate::{Game, GuessRange, Number, Outcome, Status};
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Everyone hunts the same secret; the first to find it wins.
+    #[default]
+    Shared,
+    /// Everyone has their own secret; the fewest attempts wins.
+    Race,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayersError {
+    Count(usize),
+    DuplicateName(String),
+}
+
+impl Display for PlayersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayersError::Count(n) => write!(f, "hot-seat needs {MIN_PLAYERS}-{MAX_PLAYERS} players, got {n}"),
+            PlayersError::DuplicateName(name) => write!(f, "{name} is playing twice"),
+        }
+    }
+}
+
+impl Error for PlayersError {}
+
+/// Local multiplayer: named players take turns at one keyboard. Each player
+/// gets their own `Game`, so attempts and limits are tracked per player.
+#[derive(Debug, Clone)]
+pub struct HotSeat<N = u32> {
+    names: Vec<String>,
+    games: Vec<Game<N>>,
+    variant: Variant,
+    turn: usize,
+}
+
+impl<N: Number> HotSeat<N> {
+    pub fn new<R: Rng + ?Sized>(
+        names: Vec<String>,
+        range: GuessRange<N>,
+        variant: Variant,
+        max_attempts: Option<u32>,
+        rng: &mut R,
+    ) -> Result<Self, PlayersError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&names.len()) {
+            return Err(PlayersError::Count(names.len()));
+        }
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(PlayersError::DuplicateName(name.clone()));
+            }
+        }
+        let shared = range.sample(rng);
+        let games = names
+            .iter()
+            .map(|_| {
+                let secret = match variant {
+                    Variant::Shared => shared,
+                    Variant::Race => range.sample(rng),
+                };
+                Game::with_secret_in(range, secret).with_max_attempts(max_attempts)
+            })
+            .collect();
+        Ok(HotSeat { names, games, variant, turn: 0 })
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn range(&self) -> GuessRange<N> {
+        self.games[0].range()
+    }
+
+    /// Index of the player whose turn it is.
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    pub fn name(&self, player: usize) -> &str {
+        &self.names[player]
+    }
+
+    pub fn game(&self, player: usize) -> &Game<N> {
+        &self.games[player]
+    }
+
+    pub fn players(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Guesses for the current player, then passes the turn on.
+    pub fn guess(&mut self, guess: N) -> Outcome {
+        let outcome = self.games[self.turn].guess(guess);
+        if !self.is_over() {
+            self.advance();
+        }
+        outcome
+    }
+
+    /// Everyone still playing gives up.
+    pub fn give_up(&mut self) {
+        self.games.iter_mut().for_each(Game::give_up);
+    }
+
+    pub fn is_over(&self) -> bool {
+        let won = self.games.iter().any(|g| g.status() == Status::Won);
+        let all_done = self.games.iter().all(Game::is_over);
+        match self.variant {
+            Variant::Shared => won || all_done,
+            Variant::Race => all_done,
+        }
+    }
+
+    /// Winning players: the finder in a shared game, the fewest attempts in
+    /// a race (several on a tie). Empty if nobody won.
+    pub fn winners(&self) -> Vec<usize> {
+        let won: Vec<usize> = (0..self.players()).filter(|&p| self.games[p].status() == Status::Won).collect();
+        let Some(best) = won.iter().map(|&p| self.games[p].attempts()).min() else { return won };
+        won.into_iter().filter(|&p| self.games[p].attempts() == best).collect()
+    }
+
+    fn advance(&mut self) {
+        for step in 1..=self.players() {
+            let next = (self.turn + step) % self.players();
+            if !self.games[next].is_over() {
+                self.turn = next;
+                return;
+            }
+        }
+    }
+}
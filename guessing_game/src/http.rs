@@ -0,0 +1,247 @@
+//! Small HTTP/1.1 JSON API over the game engine, for web and bot frontends.
+//!
+//! ```text
+//! POST /games                 {"difficulty"?, "min"?, "max"?, "seed"?, "max_attempts"?}
+//!                             -> 201 game state (includes "id")
+//! POST /games/{id}/guesses    {"guess": n} -> 200 {"outcome": "low"|"high"|"win", "attempts", ...}
+//! GET  /games/{id}            -> 200 game state; "secret" appears once the game is over
+//! ```
+//!
+//! Errors come back as `{"error": "..."}` with a 4xx status. Numbers are i64.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::{rng, Difficulty, Game, GuessError, GuessRange};
+
+const MAX_BODY: usize = 64 * 1024;
+
+pub struct ApiServer {
+    listener: TcpListener,
+    games: Arc<Mutex<Games>>,
+}
+
+#[derive(Default)]
+struct Games {
+    next_id: u64,
+    games: HashMap<u64, Game<i64>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NewGame {
+    difficulty: Option<String>,
+    min: Option<i64>,
+    max: Option<i64>,
+    seed: Option<u64>,
+    max_attempts: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGuess {
+    guess: i64,
+}
+
+/// A response: status code and JSON body.
+type Reply = (u16, Value);
+
+impl ApiServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(ApiServer { listener: TcpListener::bind(addr)?, games: Arc::default() })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections forever, one request per connection.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let games = Arc::clone(&self.games);
+            thread::spawn(move || {
+                let _ = serve(stream, &games);
+            });
+        }
+        Ok(())
+    }
+}
+
+fn serve(mut stream: TcpStream, games: &Mutex<Games>) -> io::Result<()> {
+    let (status, body) = match read_request(&mut stream) {
+        Ok((method, path, body)) => route(&method, &path, &body, games),
+        Err(err) => error(400, err.to_string()),
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        reason(status),
+        body.len(),
+    )?;
+    stream.flush()
+}
+
+/// Reads the request line, headers and a `Content-Length` body.
+fn read_request(stream: &mut TcpStream) -> io::Result<(String, String, Vec<u8>)> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "bad Content-Length"))?;
+        }
+    }
+    if length > MAX_BODY {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "body too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok((method, path, body))
+}
+
+fn route(method: &str, path: &str, body: &[u8], games: &Mutex<Games>) -> Reply {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let mut games = games.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    match (method, segments.as_slice()) {
+        ("POST", ["games"]) => match parse_body(body) {
+            Ok(options) => create(&mut games, options),
+            Err(reply) => reply,
+        },
+        ("GET", ["games", id]) => match lookup(&mut games, id) {
+            Ok((id, game)) => (200, state(id, game)),
+            Err(reply) => reply,
+        },
+        ("POST", ["games", id, "guesses"]) => {
+            let request: NewGuess = match parse_body(body) {
+                Ok(request) => request,
+                Err(reply) => return reply,
+            };
+            match lookup(&mut games, id) {
+                Ok((_, game)) => guess(game, request.guess),
+                Err(reply) => reply,
+            }
+        }
+        (_, ["games"] | ["games", _] | ["games", _, "guesses"]) => {
+            error(405, format!("{method} not allowed on {path}"))
+        }
+        _ => error(404, format!("no route for {path}")),
+    }
+}
+
+fn create(games: &mut Games, options: NewGame) -> Reply {
+    let difficulty = match options.difficulty.as_deref().map(str::parse::<Difficulty>) {
+        Some(Ok(difficulty)) => difficulty,
+        Some(Err(err)) => return error(422, err),
+        None => Difficulty::default(),
+    };
+    let preset = difficulty.range::<i64>();
+    let range = match GuessRange::new(options.min.unwrap_or(preset.min()), options.max.unwrap_or(preset.max())) {
+        Ok(range) => range,
+        Err(err) => return error(422, err.to_string()),
+    };
+    if options.max_attempts == Some(0) {
+        return error(422, "max_attempts must be positive");
+    }
+
+    let game = match options.seed {
+        Some(seed) => Game::in_range(range, &mut rng::seeded(seed)),
+        None => Game::in_range(range, &mut rand::thread_rng()),
+    };
+    let game = game.with_max_attempts(options.max_attempts);
+
+    games.next_id += 1;
+    let id = games.next_id;
+    let body = state(id, &game);
+    games.games.insert(id, game);
+    (201, body)
+}
+
+fn guess(game: &mut Game<i64>, guess: i64) -> Reply {
+    if game.is_over() {
+        return error(409, "game is over");
+    }
+    let range = game.range();
+    if !range.contains(guess) {
+        return error(422, GuessError::OutOfRange { guess, range }.to_string());
+    }
+    let outcome = game.guess(guess);
+    (200, json!({
+        "outcome": outcome,
+        "attempts": game.attempts(),
+        "attempts_left": game.attempts_left(),
+        "status": game.status(),
+        "secret": game.is_over().then(|| game.secret()),
+        "score": game.score(),
+    }))
+}
+
+fn state(id: u64, game: &Game<i64>) -> Value {
+    let guesses: Vec<Value> = game.history().iter()
+        .map(|(guess, outcome)| json!({ "guess": guess, "outcome": outcome }))
+        .collect();
+    json!({
+        "id": id,
+        "min": game.range().min(),
+        "max": game.range().max(),
+        "attempts": game.attempts(),
+        "max_attempts": game.max_attempts(),
+        "attempts_left": game.attempts_left(),
+        "status": game.status(),
+        "score": game.score(),
+        "guesses": guesses,
+        "secret": game.is_over().then(|| game.secret()),
+    })
+}
+
+fn lookup<'a>(games: &'a mut Games, id: &str) -> Result<(u64, &'a mut Game<i64>), Reply> {
+    let Ok(id) = id.parse() else { return Err(error(404, format!("no game {id}"))) };
+    match games.games.get_mut(&id) {
+        Some(game) => Ok((id, game)),
+        None => Err(error(404, format!("no game {id}"))),
+    }
+}
+
+/// An empty body counts as `{}`.
+fn parse_body<T: for<'de> Deserialize<'de>>(body: &[u8]) -> Result<T, Reply> {
+    let body = if body.iter().all(u8::is_ascii_whitespace) { b"{}".as_slice() } else { body };
+    serde_json::from_slice(body).map_err(|err| error(400, format!("invalid JSON: {err}")))
+}
+
+fn error(status: u16, message: impl Into<String>) -> Reply {
+    (status, json!({ "error": message.into() }))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        _ => "",
+    }
+}
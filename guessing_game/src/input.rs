@@ -0,0 +1,116 @@
+//! Line input that can give up waiting.
+//!
+//! `read_line` on stdin blocks until the player presses Enter, so a countdown
+//! could only be enforced after the fact. `TimedReader` reads on a background
+//! thread and hands lines over a channel, which lets a read stop at a deadline.
+
+use std::io::{self, BufRead, Read};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Instant;
+
+/// A `BufRead` whose reads fail with `ErrorKind::TimedOut` once the deadline
+/// passes. The line being typed at that moment is kept for the next read.
+/// Once input ends, fails or is interrupted, every read returns end of input.
+pub struct TimedReader {
+    lines: Receiver<io::Result<String>>,
+    sender: Sender<io::Result<String>>,  // for `Interrupter`; keeps `lines` connected
+    line: Vec<u8>,
+    pos: usize,
+    deadline: Option<Instant>,
+    eof: bool,
+}
+
+impl TimedReader {
+    /// Starts a thread that reads `input` line by line. The thread lives until
+    /// `input` is exhausted or the reader is dropped and another line arrives.
+    pub fn new(mut input: impl BufRead + Send + 'static) -> Self {
+        let (tx, lines) = mpsc::channel();
+        let sender = tx.clone();
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            match input.read_line(&mut line) {
+                Ok(0) => {
+                    let _ = tx.send(Ok(String::new()));  // EOF
+                    break;
+                }
+                Ok(_) => {
+                    if tx.send(Ok(line)).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    let _ = tx.send(Err(err));
+                    break;
+                }
+            }
+        });
+        TimedReader { lines, sender, line: Vec::new(), pos: 0, deadline: None, eof: false }
+    }
+
+    /// `None` waits forever, like a plain reader.
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// A handle that can end the pending read from another thread, e.g. a
+    /// Ctrl-C handler.
+    pub fn interrupter(&self) -> Interrupter {
+        Interrupter(self.sender.clone())
+    }
+}
+
+/// Makes the reader's next read return end of input, as if the player had
+/// pressed Ctrl-D.
+#[derive(Debug, Clone)]
+pub struct Interrupter(Sender<io::Result<String>>);
+
+impl Interrupter {
+    /// False once the reader is gone, when there's nobody left to stop.
+    pub fn interrupt(&self) -> bool {
+        self.0.send(Ok(String::new())).is_ok()
+    }
+}
+
+impl Read for TimedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for TimedReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.line.len() && !self.eof {
+            let received = match self.deadline {
+                Some(deadline) => self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => self.lines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            let line = match received {
+                Ok(Ok(line)) => line,
+                Ok(Err(err)) => {
+                    self.eof = true;  // the reading thread has stopped
+                    return Err(err);
+                }
+                Err(RecvTimeoutError::Timeout) => return Err(io::Error::from(io::ErrorKind::TimedOut)),
+                Err(RecvTimeoutError::Disconnected) => String::new(),  // EOF
+            };
+            self.eof = line.is_empty();
+            self.line = line.into_bytes();
+            self.pos = 0;
+        }
+        Ok(&self.line[self.pos..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = (self.pos + amount).min(self.line.len());
+    }
+}
@@ -0,0 +1,30 @@
+pub mod bot;
+pub mod code;
+mod error;
+pub mod events;
+pub mod feedback;
+mod game;
+pub mod hint;
+pub mod hotseat;
+pub mod http;
+pub mod input;
+pub mod net;
+mod range;
+pub mod replay;
+pub mod reverse;
+pub mod rng;
+pub mod save;
+pub mod score;
+mod session;
+pub mod simulate;
+pub mod spectate;
+pub mod stats;
+pub mod strategy;
+pub mod tournament;
+pub mod tui;
+pub mod word;
+
+pub use error::GuessError;
+pub use game::{Game, Outcome, Status};
+pub use range::{Difficulty, GuessRange, InvalidRange, Number};
+pub use session::Session;
@@ -1,27 +1,438 @@
-use std::io;  // For input
-use rand::Rng;  // Add to Cargo.toml: rand = "0.8.5", then cargo build
+mod cli;
+
+use std::io::{self, IsTerminal};  // For input
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+use clap::{CommandFactory, Parser};
+use rand::RngCore;
+use guessing_game::bot::ExternalBot;
+use guessing_game::code::{self, CodeGame};
+use guessing_game::input::TimedReader;
+use guessing_game::hotseat::{HotSeat, Variant};
+use guessing_game::http::ApiServer;
+use guessing_game::net::{Client, Server};
+use guessing_game::replay::{self, Log, Pace, Recorder, Setup};
+use guessing_game::save::{self, SaveSlot, SavedGame};
+use guessing_game::simulate::{self, Report};
+use guessing_game::spectate::Spectators;
+use guessing_game::stats::{self, Stats};
+use guessing_game::tournament::{Format, Tournament};
+use guessing_game::tui::Tui;
+use guessing_game::word::{Dictionary, WordGame};
+use guessing_game::{feedback, rng, score, strategy, Game, GuessRange, Number, Session, Status};
+use cli::{
+    Bounds, Cli, CodeArgs, Command, ConnectArgs, GameArgs, HotseatArgs, PlayArgs, ReplayArgs, ResumeArgs, ServeArgs,
+    SimulateArgs, TournamentArgs, WordArgs,
+};
 
 fn main() {
-    println!("Guess the number (1-100)!");
+    match Cli::parse().into_command() {
+        Command::Play(args) if args.resume_latest => resume_latest(&args),
+        Command::Play(args) => match bounds(&args.game.range) {
+            Bounds::Signed(range) => play(range, &args),
+            Bounds::Unsigned(range) => play(range, &args),
+        },
+        Command::Stats => show_stats(),
+        Command::Hotseat(args) => match bounds(&args.game.range) {
+            Bounds::Signed(range) => play_hotseat(range, args),
+            Bounds::Unsigned(range) => play_hotseat(range, args),
+        },
+        Command::Tournament(args) => match bounds(&args.game.range) {
+            Bounds::Signed(range) => play_tournament(range, args),
+            Bounds::Unsigned(range) => play_tournament(range, args),
+        },
+        Command::Serve(args) => match bounds(&args.range) {
+            Bounds::Signed(range) => serve(range, &args),
+            Bounds::Unsigned(range) => serve(range, &args),
+        },
+        Command::Connect(args) => connect(&args),
+        Command::Http { bind } => serve_http(&bind),
+        Command::Reverse(args) => match bounds(&args) {
+            Bounds::Signed(range) => play_reverse(range),
+            Bounds::Unsigned(range) => play_reverse(range),
+        },
+        Command::Simulate(args) => match bounds(&args.game.range) {
+            Bounds::Signed(range) => simulate(range, &args),
+            Bounds::Unsigned(range) => simulate(range, &args),
+        },
+        Command::Code(args) => play_code(&args),
+        Command::Words(args) => play_words(&args),
+        Command::Resume(args) => resume(&args.file, &Frontend::from(&args)),
+        Command::Replay(args) => replay(&args),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "guessing_game", &mut io::stdout());
+        }
+    }
+}
+
+fn bounds(args: &cli::RangeArgs) -> Bounds {
+    args.bounds().unwrap_or_else(|err| exit_with(&err))
+}
 
-    let secret = rand::thread_rng().gen_range(1..=100);
+fn secret_rng(seed: Option<u64>) -> Box<dyn RngCore> {
+    match seed {
+        Some(seed) => Box::new(rng::seeded(seed)),
+        None => Box::new(rand::thread_rng()),
+    }
+}
 
-    loop {
-        let mut guess = String::new();
-        io::stdin().read_line(&mut guess).expect("Failed to read line");
+/// How a number game is shown; shared by new and resumed games.
+#[derive(Clone)]
+struct Frontend<'a> {
+    spectate: Option<&'a str>,
+    plain: bool,
+    speedrun: bool,
+    feedback: &'a str,
+    record: Option<(&'a Path, Setup)>,
+    bot: Option<(&'a str, Duration)>,
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
+impl<'a> From<&'a PlayArgs> for Frontend<'a> {
+    fn from(args: &'a PlayArgs) -> Self {
+        Frontend {
+            spectate: args.spectate.as_deref(),
+            plain: args.plain,
+            speedrun: args.speedrun,
+            feedback: &args.feedback,
+            record: None,
+            bot: args.bot.as_deref().map(|command| (command, args.bot_timeout)),
+        }
+    }
+}
+
+impl<'a> From<&'a ResumeArgs> for Frontend<'a> {
+    fn from(args: &'a ResumeArgs) -> Self {
+        // The save says which feedback to use.
+        Frontend { spectate: None, plain: args.plain, speedrun: false, feedback: feedback::NAMES[0], record: None, bot: None }
+    }
+}
+
+fn play<N: Number>(range: GuessRange<N>, args: &PlayArgs) {
+    // Other feedback is worked out from the real number, so it can't lie.
+    if args.lies > 0 && args.feedback != feedback::NAMES[0] {
+        exit_with(&format!("--lies needs low-high feedback, not {}", args.feedback));
+    }
+    let GameArgs { seed, max_attempts, .. } = args.game;
+    // The game is built from its setup so a recording replays it exactly.
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().next_u64());
+    let setup = Setup::new(range, seed).with_max_attempts(max_attempts).with_time_limit(args.time_limit).with_lies(args.lies);
+    let game = setup.game::<N>().expect("the setup's range came from N");
+    // Bots can't pick a game back up, so theirs aren't saved.
+    let slot = save::default_dir()
+        .filter(|_| args.bot.is_none())
+        .map(|dir| SaveSlot::new_in(&dir, Some(seed)).with_feedback(&args.feedback));
+    let mut frontend = Frontend::from(args);
+    frontend.record = args.record.as_deref().map(|path| (path, setup));
+    play_game(game, slot, &frontend);
+}
+
+fn resume_latest(args: &PlayArgs) {
+    let dir = save::default_dir().unwrap_or_else(|| exit_with("cannot locate the data directory"));
+    match save::latest(&dir) {
+        Ok(Some(path)) => resume(&path, &Frontend::from(args)),
+        Ok(None) => exit_with("no unfinished game to continue"),
+        Err(err) => exit_with(&format!("{}: {err}", dir.display())),
+    }
+}
+
+fn resume(path: &Path, frontend: &Frontend) {
+    let saved = SavedGame::load(path).unwrap_or_else(|err| exit_with(&format!("cannot load {}: {err}", path.display())));
+    let slot = SaveSlot { path: path.to_path_buf(), seed: saved.seed, feedback: saved.feedback.clone() };
+    let frontend = Frontend { feedback: &saved.feedback, ..frontend.clone() };
+    match Bounds::new(saved.min, saved.max).unwrap_or_else(|err| exit_with(&err)) {
+        Bounds::Signed(_) => resume_as::<i64>(&saved, slot, &frontend),
+        Bounds::Unsigned(_) => resume_as::<u64>(&saved, slot, &frontend),
+    }
+}
+
+fn resume_as<N: Number>(saved: &SavedGame, slot: SaveSlot, frontend: &Frontend) {
+    let game = saved.restore::<N>()
+        .unwrap_or_else(|err| exit_with(&format!("cannot resume {}: {err}", slot.path.display())));
+    play_game(game, Some(slot), frontend);
+}
 
-        match guess.cmp(&secret) {
-            std::cmp::Ordering::Less => println!("Too low!"),
-            std::cmp::Ordering::Greater => println!("Too high!"),
-            std::cmp::Ordering::Equal => {
-                println!("You win!");
-                break;
+fn play_game<N: Number>(mut game: Game<N>, slot: Option<SaveSlot>, frontend: &Frontend) {
+    let spectators = frontend.spectate.map(|addr| {
+        let spectators = Spectators::bind(addr)
+            .unwrap_or_else(|err| exit_with(&format!("cannot listen on {addr}: {err}")));
+        println!("Spectators can watch at {}", spectators.url());
+        spectators
+    });
+    let recorder = frontend.record.as_ref().map(|(path, setup)| {
+        Recorder::create(path, setup).unwrap_or_else(|err| exit_with(&format!("cannot create {}: {err}", path.display())))
+    });
+
+    // The full-screen UI needs a terminal on both ends; pipes get plain lines.
+    // Its interval bar gives low/high (and lies) away, so other feedback and
+    // lying games play plain too.
+    let full_screen = !frontend.plain
+        && frontend.bot.is_none()
+        && frontend.feedback == feedback::NAMES[0]
+        && game.max_lies() == 0
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal();
+    let result = if let Some((command, timeout)) = frontend.bot {
+        let mut session = Session::new(io::empty(), io::stdout().lock());
+        if let Some(spectators) = &spectators {
+            session = session.with_observer(spectators.observer());
+        }
+        if let Some(recorder) = &recorder {
+            session = session.with_observer(recorder.observer());
+        }
+        let mut bot = ExternalBot::spawn(command)
+            .unwrap_or_else(|err| exit_with(&format!("{command}: {err}")))
+            .with_timeout(timeout);
+        match session.play_bot(&mut game, &mut bot) {
+            Ok(None) => Ok(()),
+            Ok(Some(forfeit)) => Err(format!("{command}: {forfeit}")),
+            Err(err) => Err(err.to_string()),
+        }
+    } else if full_screen {
+        let mut tui = Tui::new();
+        if let Some(spectators) = &spectators {
+            tui = tui.with_observer(spectators.observer());
+        }
+        if let Some(recorder) = &recorder {
+            tui = tui.with_observer(recorder.observer());
+        }
+        if let Some(slot) = &slot {
+            tui = tui.with_save_slot(slot.clone());
+        }
+        tui.play(&mut game).map_err(|err| err.to_string())
+    } else {
+        // Under a countdown, reads must be able to stop at the deadline.
+        game.start_clock();
+        let input = TimedReader::new(io::BufReader::new(io::stdin())).with_deadline(game.deadline());
+        // Ctrl-C ends input like Ctrl-D, which saves the game or gives up;
+        // once the game is over it just quits.
+        let interrupter = input.interrupter();
+        if let Err(err) = ctrlc::set_handler(move || {
+            if !interrupter.interrupt() {
+                process::exit(130);
             }
+        }) {
+            eprintln!("warning: Ctrl-C will quit without saving: {err}");
+        }
+        let mut session = Session::new(input, io::stdout().lock());
+        if let Some(spectators) = &spectators {
+            session = session.with_observer(spectators.observer());
+        }
+        if let Some(recorder) = &recorder {
+            session = session.with_observer(recorder.observer());
+        }
+        if frontend.speedrun {
+            session = session.with_stopwatch();
+        }
+        if let Some(slot) = &slot {
+            session = session.with_save_slot(slot.clone());
+        }
+        session = session.with_feedback(feedback::by_name(frontend.feedback).expect("names are validated by the CLI"));
+        session.play(&mut game).map_err(|err| err.to_string())
+    };
+
+    if let Some(spectators) = spectators {
+        spectators.finish();
+    }
+    if let Some(Err(err)) = recorder.map(Recorder::finish) {
+        eprintln!("warning: the game log is incomplete: {err}");
+    }
+    if let Err(err) = result {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+    if !game.is_over() {
+        if full_screen && let Some(slot) = &slot {
+            println!("Game saved to {}; pick it up with --continue.", slot.path.display());
+        }
+        return;  // saved for later; stats wait until it's finished
+    }
+    if full_screen {
+        let hints = match game.penalty() {
+            0 => String::new(),
+            penalty => format!(" + {penalty} for hints"),
+        };
+        println!("The number was {}. Attempts: {}{hints}, score: {}", game.secret(), game.attempts(), game.score());
+        if frontend.speedrun && game.status() == Status::Won {
+            println!("Time: {:.3}s", game.elapsed().as_secs_f64());
+        }
+    }
+    if game.attempts() > 0 && frontend.bot.is_none() {
+        record_stats(&game, frontend.speedrun);  // don't count games abandoned before the first guess
+    }
+}
+
+fn replay(args: &ReplayArgs) {
+    let log = Log::load(&args.file).unwrap_or_else(|err| exit_with(&format!("cannot load {}: {err}", args.file.display())));
+    if !args.check {
+        let pace = if args.step { Pace::Step } else { Pace::Timed(args.speed) };
+        if let Err(err) = replay::play_back(&log, io::stdin().lock(), io::stdout().lock(), pace) {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    }
+    let verified = match Bounds::new(log.setup.min, log.setup.max) {
+        Ok(Bounds::Signed(_)) => log.verify::<i64>(),
+        Ok(Bounds::Unsigned(_)) => log.verify::<u64>(),
+        Err(err) => Err(err),
+    };
+    match verified {
+        Ok(()) if log.is_complete() => println!("Verified: every answer matches the game for seed {}.", log.setup.seed),
+        Ok(()) => println!("Verified so far: every answer matches, but the log stops before the game ended."),
+        Err(err) => {
+            println!("Mismatch: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+fn play_hotseat<N: Number>(range: GuessRange<N>, args: HotseatArgs) {
+    let variant = if args.race { Variant::Race } else { Variant::Shared };
+    let mut rng = secret_rng(args.game.seed);
+    let mut hotseat = HotSeat::new(args.players, range, variant, args.game.max_attempts, &mut rng)
+        .unwrap_or_else(|err| exit_with(&err.to_string()));
+    let mut session = Session::new(io::stdin().lock(), io::stdout().lock());
+
+    if let Err(err) = session.play_hotseat(&mut hotseat) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn play_tournament<N: Number>(range: GuessRange<N>, args: TournamentArgs) {
+    let format = if args.knockout { Format::Knockout } else { Format::RoundRobin };
+    // Always seeded, so a tournament can be rerun with the same secrets.
+    let seed = args.game.seed.unwrap_or_else(|| rand::thread_rng().next_u64());
+    let mut tournament = Tournament::new(args.players, format, range, args.game.max_attempts, seed)
+        .unwrap_or_else(|err| exit_with(&err.to_string()))
+        .with_bot_timeout(args.bot_timeout);
+    let mut session = Session::new(io::stdin().lock(), io::stdout().lock());
+
+    if let Err(err) = session.play_tournament(&mut tournament) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn serve<N: Number>(range: GuessRange<N>, args: &ServeArgs) {
+    let server = Server::bind(&args.bind, range, args.seed)
+        .unwrap_or_else(|err| exit_with(&format!("cannot listen on {}: {err}", args.bind)));
+    println!("Serving {range} on {}", server.local_addr().map_or(args.bind.clone(), |addr| addr.to_string()));
+    if let Err(err) = server.run() {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn connect(args: &ConnectArgs) {
+    let client = Client::connect(&args.addr)
+        .unwrap_or_else(|err| exit_with(&format!("cannot connect to {}: {err}", args.addr)));
+    if let Err(err) = client.run(&args.name, io::stdin().lock(), io::stdout()) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn serve_http(bind: &str) {
+    let server = ApiServer::bind(bind).unwrap_or_else(|err| exit_with(&format!("cannot listen on {bind}: {err}")));
+    println!("HTTP API on http://{}", server.local_addr().map_or(bind.to_string(), |addr| addr.to_string()));
+    if let Err(err) = server.run() {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn play_reverse<N: Number>(range: GuessRange<N>) {
+    let mut session = Session::new(io::stdin().lock(), io::stdout().lock());
+    if let Err(err) = session.play_reverse(range) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn simulate<N: Number>(range: GuessRange<N>, args: &SimulateArgs) {
+    let seed = args.game.seed.unwrap_or_else(|| rand::thread_rng().next_u64());
+    let names: Vec<&str> = if args.strategies.is_empty() {
+        strategy::NAMES.to_vec()
+    } else {
+        args.strategies.iter().map(String::as_str).collect()
+    };
+
+    println!("{} games on {range}, seed {seed}; binary search needs at most {} attempts",
+             args.games, score::optimal_attempts(range.size()));
+    println!("{}", Report::HEADER);
+    for name in names {
+        let mut strategy = strategy::by_name(name).expect("names are validated by the CLI");
+        println!("{}", simulate::simulate(strategy.as_mut(), range, args.game.max_attempts, args.lies, args.games, seed));
+    }
+}
+
+fn play_code(args: &CodeArgs) {
+    let rules = code::Rules::new(args.length.into(), args.repeats).unwrap_or_else(|err| exit_with(&err));
+    let mut game = CodeGame::new(rules, &mut secret_rng(args.seed)).with_max_attempts(args.max_attempts);
+    if !args.auto {
+        let mut session = Session::new(io::stdin().lock(), io::stdout().lock());
+        if let Err(err) = session.play_code(&mut game) {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mut solver = code::Solver::new(rules).unwrap_or_else(|err| exit_with(&err));
+    println!("Cracking the code ({rules})...");
+    while !game.is_over() {
+        let Some(guess) = solver.next_guess() else { break };
+        let score = game.guess(&guess);
+        solver.feedback(&guess, score);
+        println!("{guess}: {score} ({} possible)", solver.candidates().len());
+    }
+    match game.status() {
+        Status::Won => println!("Cracked {} in {} attempts.", game.secret(), game.attempts()),
+        _ => println!("Out of attempts, the code was {}.", game.secret()),
+    }
+}
+
+fn play_words(args: &WordArgs) {
+    let length = usize::from(args.length);
+    let dictionary = match &args.words {
+        Some(path) => Dictionary::load(path, length)
+            .unwrap_or_else(|err| exit_with(&format!("cannot load {}: {err}", path.display()))),
+        None if length == 5 => Dictionary::builtin(),
+        None => exit_with("the built-in word list only has five-letter words; pass --words"),
+    };
+    let mut game = WordGame::new(dictionary, &mut secret_rng(args.seed))
+        .with_max_attempts(Some(args.max_attempts))
+        .with_hard_mode(args.hard);
+
+    let mut session = Session::new(io::stdin().lock(), io::stdout().lock());
+    if let Err(err) = session.play_words(&mut game) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn record_stats<N: Number>(game: &Game<N>, speedrun: bool) {
+    let Some(path) = Stats::default_path() else { return };
+    let result = Stats::load(&path).and_then(|mut stats| {
+        stats.record(game);
+        if speedrun && stats.record_speedrun(game) {
+            println!("New personal best for {}!", stats::category(game.range()));
         }
+        stats.save(&path)
+    });
+    if let Err(err) = result {
+        eprintln!("warning: could not update {}: {err}", path.display());
     }
 }
+
+fn show_stats() {
+    let path = Stats::default_path().unwrap_or_else(|| exit_with("cannot locate the data directory"));
+    let stats = Stats::load(&path).unwrap_or_else(|err| exit_with(&format!("{}: {err}", path.display())));
+    stats.write_report(io::stdout().lock()).expect("Failed to write stats");
+}
+
+fn exit_with(err: &str) -> ! {
+    eprintln!("{err}");
+    process::exit(2);
+}
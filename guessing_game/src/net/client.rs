@@ -0,0 +1,87 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+
+/// A connection to a `Server`, speaking raw protocol lines.
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    /// Connects and consumes the server's `HELLO`.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        let mut client = Client { reader: BufReader::new(stream.try_clone()?), writer: stream };
+        match client.recv()? {
+            Some(hello) if hello.starts_with("HELLO ") => Ok(client),
+            other => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unexpected greeting {other:?}"))),
+        }
+    }
+
+    pub fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.writer, "{line}")
+    }
+
+    /// Next line from the server, or `None` once it hangs up.
+    pub fn recv(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end().to_string()))
+    }
+
+    /// Sends one request and returns the first reply.
+    pub fn request(&mut self, line: &str) -> io::Result<Option<String>> {
+        self.send(line)?;
+        self.recv()
+    }
+
+    /// Interactive terminal client: numbers typed are sent as guesses,
+    /// anything else as a raw command; server lines are printed as they come.
+    pub fn run<R: BufRead, W: Write + Send + 'static>(self, name: &str, input: R, mut output: W) -> io::Result<()> {
+        let Client { mut reader, mut writer } = self;
+        writeln!(writer, "JOIN {name}")?;
+
+        let printer = thread::spawn(move || -> io::Result<()> {
+            let mut line = String::new();
+            while reader.read_line(&mut line)? > 0 {
+                writeln!(output, "{}", describe(line.trim_end()))?;
+                output.flush()?;
+                line.clear();
+            }
+            Ok(())
+        });
+
+        for line in input.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.parse::<i128>().is_ok() {
+                writeln!(writer, "GUESS {line}")?;
+            } else if !line.is_empty() {
+                writeln!(writer, "{line}")?;
+            }
+        }
+        writeln!(writer, "QUIT")?;
+        printer.join().expect("printer thread panicked")
+    }
+}
+
+/// Turns protocol lines into friendlier text for people.
+pub fn describe(line: &str) -> String {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["LOW"] => "Too low!".to_string(),
+        ["HIGH"] => "Too high!".to_string(),
+        ["WIN", attempts] => format!("You win! ({attempts} attempts)"),
+        ["WELCOME", name] => format!("Welcome, {name}. CREATE <room> or ENTER <room>; ROOMS lists them."),
+        ["ENTERED", room, min, max] => format!("In room {room}: guess the number ({min}-{max})!"),
+        ["ROUND", min, max] => format!("New round: guess the number ({min}-{max})!"),
+        ["EVENT", "GUESS", player, guess, outcome] => format!("{player} guessed {guess}: {}", outcome.to_lowercase()),
+        ["EVENT", "WON", player, attempts, secret] => format!("{player} found {secret} in {attempts} attempts."),
+        ["ERR", ..] => format!("Error: {}", &line[4..]),
+        _ => line.to_string(),
+    }
+}
@@ -0,0 +1,24 @@
+//! LAN multiplayer over TCP with a line-based text protocol.
+//!
+//! Client requests, one per line (case-insensitive verbs):
+//!
+//! ```text
+//! JOIN <name>      pick a name; required before anything else
+//! ROOMS            list rooms and how many players are in each
+//! CREATE <room>    open a room with a fresh secret and enter it
+//! ENTER <room>     enter an existing room
+//! LEAVE            go back to the lobby
+//! GUESS <n>        answered with LOW, HIGH or WIN <attempts>
+//! QUIT             close the connection
+//! ```
+//!
+//! Everyone in a room hunts the same secret. Other players' guesses arrive
+//! as `EVENT ...` lines, and a win starts a new `ROUND <min> <max>` for the
+//! whole room. Problems are reported as `ERR <message>`.
+
+pub mod client;
+pub mod protocol;
+pub mod server;
+
+pub use client::Client;
+pub use server::Server;
@@ -0,0 +1,101 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use crate::Outcome;
+
+/// A line sent by a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    Join(String),
+    Rooms,
+    Create(String),
+    Enter(String),
+    Leave,
+    Guess(String),  // parsed against the room's range by the server
+    Quit,
+}
+
+impl FromStr for Request {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut words = line.split_whitespace();
+        let verb = words.next().ok_or("empty request")?.to_ascii_uppercase();
+        let mut arg = |what: &str| match words.next() {
+            Some(word) => Ok(word.to_string()),
+            None => Err(format!("{verb} needs a {what}")),
+        };
+
+        let request = match verb.as_str() {
+            "JOIN" => Request::Join(arg("name")?),
+            "ROOMS" => Request::Rooms,
+            "CREATE" => Request::Create(arg("room name")?),
+            "ENTER" => Request::Enter(arg("room name")?),
+            "LEAVE" => Request::Leave,
+            "GUESS" => Request::Guess(arg("number")?),
+            "QUIT" => Request::Quit,
+            _ => return Err(format!("unknown command {verb}")),
+        };
+        match words.next() {
+            Some(extra) => Err(format!("unexpected `{extra}` after {verb}")),
+            None => Ok(request),
+        }
+    }
+}
+
+/// A line sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response<N> {
+    Hello,
+    Welcome(String),
+    Rooms(Vec<(String, usize)>),
+    Entered { room: String, min: N, max: N },
+    Left(String),
+    Feedback { outcome: Outcome, attempts: u32 },
+    Joined { room: String, player: String },
+    Departed { room: String, player: String },
+    Guessed { player: String, guess: N, outcome: Outcome },
+    Won { player: String, attempts: u32, secret: N },
+    Round { min: N, max: N },
+    Error(String),
+    Bye,
+}
+
+impl<N: Display> Display for Response<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Hello => write!(f, "HELLO guessing_game {}", env!("CARGO_PKG_VERSION")),
+            Response::Welcome(name) => write!(f, "WELCOME {name}"),
+            Response::Rooms(rooms) => {
+                write!(f, "ROOMS")?;
+                rooms.iter().try_for_each(|(room, players)| write!(f, " {room}:{players}"))
+            }
+            Response::Entered { room, min, max } => write!(f, "ENTERED {room} {min} {max}"),
+            Response::Left(room) => write!(f, "LEFT {room}"),
+            Response::Feedback { outcome: Outcome::Low, .. } => write!(f, "LOW"),
+            Response::Feedback { outcome: Outcome::High, .. } => write!(f, "HIGH"),
+            Response::Feedback { outcome: Outcome::Win, attempts } => write!(f, "WIN {attempts}"),
+            Response::Joined { room, player } => write!(f, "EVENT {room} JOINED {player}"),
+            Response::Departed { room, player } => write!(f, "EVENT {room} LEFT {player}"),
+            Response::Guessed { player, guess, outcome } => {
+                write!(f, "EVENT GUESS {player} {guess} {}", outcome_word(*outcome))
+            }
+            Response::Won { player, attempts, secret } => write!(f, "EVENT WON {player} {attempts} {secret}"),
+            Response::Round { min, max } => write!(f, "ROUND {min} {max}"),
+            Response::Error(message) => write!(f, "ERR {message}"),
+            Response::Bye => write!(f, "BYE"),
+        }
+    }
+}
+
+pub fn outcome_word(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Low => "LOW",
+        Outcome::High => "HIGH",
+        Outcome::Win => "WIN",
+    }
+}
+
+/// Names and room names: short, printable, no spaces or separators.
+pub fn valid_name(name: &str) -> bool {
+    (1..=24).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use rand::RngCore;
+use super::protocol::{self, Request, Response};
+use crate::{rng, Game, GuessRange, Number, Outcome};
+
+/// Hosts any number of rooms; each connection gets its own thread.
+pub struct Server<N = i64> {
+    listener: TcpListener,
+    lobby: Arc<Mutex<Lobby<N>>>,
+}
+
+struct Lobby<N> {
+    range: GuessRange<N>,
+    rng: Box<dyn RngCore + Send>,
+    names: HashSet<String>,
+    rooms: BTreeMap<String, Room<N>>,
+}
+
+struct Room<N> {
+    secret: N,
+    members: BTreeMap<String, Member<N>>,
+}
+
+struct Member<N> {
+    game: Game<N>,
+    tx: Sender<String>,
+}
+
+impl<N: Number> Server<N> {
+    /// Binds without accepting yet; use port 0 to let the OS pick one.
+    pub fn bind<A: ToSocketAddrs>(addr: A, range: GuessRange<N>, seed: Option<u64>) -> io::Result<Self> {
+        let rng: Box<dyn RngCore + Send> = match seed {
+            Some(seed) => Box::new(rng::seeded(seed)),
+            None => Box::new(rng::seeded(rand::random())),
+        };
+        let lobby = Lobby { range, rng, names: HashSet::new(), rooms: BTreeMap::new() };
+        Ok(Server { listener: TcpListener::bind(addr)?, lobby: Arc::new(Mutex::new(lobby)) })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections forever.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let lobby = Arc::clone(&self.lobby);
+            thread::spawn(move || {
+                let _ = serve(stream, lobby);  // a dropped client is not a server error
+            });
+        }
+        Ok(())
+    }
+}
+
+fn serve<N: Number>(stream: TcpStream, lobby: Arc<Mutex<Lobby<N>>>) -> io::Result<()> {
+    // Replies and room broadcasts share one queue so lines never interleave.
+    stream.set_nodelay(true)?;  // one short line per reply
+    let (tx, rx) = mpsc::channel::<String>();
+    let mut writer = stream.try_clone()?;
+    let writer_thread = thread::spawn(move || {
+        for line in rx {
+            if writeln!(writer, "{line}").is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut connection = Connection { lobby, tx, name: None, room: None };
+    connection.send(Response::Hello);
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.parse() {
+            Ok(Request::Quit) => {
+                connection.send(Response::Bye);
+                break;
+            }
+            Ok(request) => connection.handle(request),
+            Err(message) => connection.send(Response::Error(message)),
+        }
+    }
+
+    connection.disconnect();
+    drop(connection);  // closes the queue once no room holds a sender
+    let _ = writer_thread.join();
+    Ok(())
+}
+
+/// Per-client state, living on the connection's thread.
+struct Connection<N> {
+    lobby: Arc<Mutex<Lobby<N>>>,
+    tx: Sender<String>,
+    name: Option<String>,
+    room: Option<String>,
+}
+
+impl<N: Number> Connection<N> {
+    /// Handlers send their own replies; an `Err` becomes an `ERR` line.
+    fn handle(&mut self, request: Request) {
+        let result = match (self.name.clone(), request) {
+            (None, Request::Join(name)) => self.join(name),
+            (None, _) => Err("JOIN with a name first".to_string()),
+            (Some(_), Request::Join(_)) => Err("already joined".to_string()),
+            (Some(_), Request::Rooms) => {
+                let lobby = lock(&self.lobby);
+                let rooms = lobby.rooms.iter().map(|(room, r)| (room.clone(), r.members.len()));
+                self.send(Response::Rooms(rooms.collect()));
+                Ok(())
+            }
+            (Some(name), Request::Create(room)) => self.enter(name, room, true),
+            (Some(name), Request::Enter(room)) => self.enter(name, room, false),
+            (Some(name), Request::Leave) => match self.room.take() {
+                Some(room) => {
+                    lock(&self.lobby).leave(&room, &name);
+                    self.send(Response::Left(room));
+                    Ok(())
+                }
+                None => Err("not in a room".to_string()),
+            },
+            (Some(name), Request::Guess(input)) => self.guess(name, &input),
+            (_, Request::Quit) => unreachable!("handled by serve"),
+        };
+        if let Err(message) = result {
+            self.send(Response::Error(message));
+        }
+    }
+
+    fn join(&mut self, name: String) -> Result<(), String> {
+        if !protocol::valid_name(&name) {
+            return Err(format!("invalid name {name}"));
+        }
+        if !lock(&self.lobby).names.insert(name.clone()) {
+            return Err(format!("name {name} is taken"));
+        }
+        self.send(Response::Welcome(name.clone()));
+        self.name = Some(name);
+        Ok(())
+    }
+
+    fn enter(&mut self, name: String, room: String, create: bool) -> Result<(), String> {
+        if !protocol::valid_name(&room) {
+            return Err(format!("invalid room name {room}"));
+        }
+        let mut lobby = lock(&self.lobby);
+        match (create, lobby.rooms.contains_key(&room)) {
+            (true, true) => return Err(format!("room {room} exists")),
+            (false, false) => return Err(format!("no room {room}")),
+            _ => {}
+        }
+        if let Some(old) = self.room.take() {
+            lobby.leave(&old, &name);
+        }
+
+        let Lobby { range, rng, rooms, .. } = &mut *lobby;
+        let target = rooms.entry(room.clone()).or_insert_with(|| {
+            Room { secret: range.sample(rng), members: BTreeMap::new() }
+        });
+        target.broadcast(Response::Joined { room: room.clone(), player: name.clone() });
+        let game = Game::with_secret_in(*range, target.secret);
+        target.members.insert(name, Member { game, tx: self.tx.clone() });
+
+        self.send(Response::Entered { room: room.clone(), min: range.min(), max: range.max() });
+        self.room = Some(room);
+        Ok(())
+    }
+
+    fn guess(&self, name: String, input: &str) -> Result<(), String> {
+        let room_name = self.room.as_ref().ok_or("enter a room first")?;
+        let mut lobby = lock(&self.lobby);
+        let Lobby { range, rng, rooms, .. } = &mut *lobby;
+        let guess = range.parse(input).map_err(|err| err.to_string())?;
+
+        let room = rooms.get_mut(room_name).expect("members' rooms exist");
+        let member = room.members.get_mut(&name).expect("member of own room");
+        let outcome = member.game.guess(guess);
+        let attempts = member.game.attempts();
+
+        self.send(Response::Feedback { outcome, attempts });  // ahead of the ROUND broadcast
+        room.broadcast_except(&name, Response::Guessed { player: name.clone(), guess, outcome });
+        if outcome == Outcome::Win {
+            room.broadcast_except(&name, Response::Won { player: name.clone(), attempts, secret: room.secret });
+            room.secret = range.sample(rng);
+            for member in room.members.values_mut() {
+                member.game = Game::with_secret_in(*range, room.secret);
+            }
+            room.broadcast(Response::Round { min: range.min(), max: range.max() });
+        }
+        Ok(())
+    }
+
+    fn disconnect(&mut self) {
+        let Some(name) = self.name.take() else { return };
+        let mut lobby = lock(&self.lobby);
+        if let Some(room) = self.room.take() {
+            lobby.leave(&room, &name);
+        }
+        lobby.names.remove(&name);
+    }
+
+    fn send(&self, response: Response<N>) {
+        let _ = self.tx.send(response.to_string());
+    }
+}
+
+fn lock<N>(lobby: &Mutex<Lobby<N>>) -> MutexGuard<'_, Lobby<N>> {
+    lobby.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl<N: Number> Lobby<N> {
+    /// Removes a member; empty rooms close.
+    fn leave(&mut self, room_name: &str, name: &str) {
+        let Some(room) = self.rooms.get_mut(room_name) else { return };
+        room.members.remove(name);
+        if room.members.is_empty() {
+            self.rooms.remove(room_name);
+        } else {
+            room.broadcast(Response::Departed { room: room_name.to_string(), player: name.to_string() });
+        }
+    }
+}
+
+impl<N: Number> Room<N> {
+    fn broadcast(&self, response: Response<N>) {
+        self.broadcast_except("", response);
+    }
+
+    fn broadcast_except(&self, skip: &str, response: Response<N>) {
+        let line = response.to_string();
+        for (name, member) in &self.members {
+            if name != skip {
+                let _ = member.tx.send(line.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+use rand::Rng;
+use rand::distributions::uniform::SampleUniform;
+use crate::error::GuessError;
+
+/// Integer types a secret can be drawn from.
+pub trait Number:
+    Copy + Ord + Debug + Display + FromStr + SampleUniform + From<u32> + Send + Sync + 'static
+{
+    /// Lossless widening, used for range arithmetic.
+    fn to_i128(self) -> i128;
+
+    /// Narrowing back; `None` if `n` does not fit.
+    fn from_i128(n: i128) -> Option<Self>;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                <$t>::try_from(n).ok()
+            }
+        })*
+    };
+}
+
+impl_number!(u32, u64, i64);
+
+/// Inclusive range the secret is drawn from; also drives the prompt and
+/// which guesses are accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessRange<N = u32> {
+    min: N,
+    max: N,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRange;
+
+impl Display for InvalidRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "min must not be greater than max")
+    }
+}
+
+impl Error for InvalidRange {}
+
+impl<N: Number> GuessRange<N> {
+    pub fn new(min: N, max: N) -> Result<Self, InvalidRange> {
+        if min > max {
+            return Err(InvalidRange);
+        }
+        Ok(GuessRange { min, max })
+    }
+
+    pub fn min(&self) -> N {
+        self.min
+    }
+
+    pub fn max(&self) -> N {
+        self.max
+    }
+
+    /// How many values the range holds.
+    pub fn size(&self) -> u128 {
+        (self.max.to_i128() - self.min.to_i128()) as u128 + 1
+    }
+
+    pub fn contains(&self, n: N) -> bool {
+        self.min <= n && n <= self.max
+    }
+
+    /// Parses a line of input into a guess inside this range.
+    pub fn parse(&self, input: &str) -> Result<N, GuessError<N>> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let guess: N = input.parse().map_err(|_| GuessError::NotANumber(input.to_string()))?;
+        if !self.contains(guess) {
+            return Err(GuessError::OutOfRange { guess, range: *self });
+        }
+        Ok(guess)
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> N {
+        rng.gen_range(self.min..=self.max)
+    }
+}
+
+impl Default for GuessRange {
+    fn default() -> Self {
+        Difficulty::Normal.range()
+    }
+}
+
+impl<N: Number> Display for GuessRange<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.min < N::from(0) {
+            write!(f, "{} to {}", self.min, self.max)  // "-5--1" reads badly
+        } else {
+            write!(f, "{}-{}", self.min, self.max)
+        }
+    }
+}
+
+/// Named range presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] =
+        [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Insane];
+
+    pub fn bounds(self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (1, 10),
+            Difficulty::Normal => (1, 100),
+            Difficulty::Hard => (1, 1_000),
+            Difficulty::Insane => (1, 1_000_000),
+        }
+    }
+
+    pub fn range<N: Number>(self) -> GuessRange<N> {
+        let (min, max) = self.bounds();
+        GuessRange { min: N::from(min), max: N::from(max) }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|d| d.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown difficulty `{s}`, expected easy, normal, hard or insane"))
+    }
+}
@@ -0,0 +1,337 @@
+//! Game logs for replays and disputes.
+//!
+//! A log is JSON Lines: a `start` record with everything needed to recreate
+//! the secret, one record per guess or hint with its time, and an `end`
+//! record. Replaying a seeded game through the engine shows whether every
+//! recorded answer was the one the game would give.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use crate::events::{GameEvent, LossReason};
+use crate::hint::Hint;
+use crate::{rng, Game, GuessRange, Number, Outcome, Status};
+
+/// Bumped when the format changes incompatibly.
+pub const VERSION: u32 = 1;
+
+/// How a game was set up. Games built with `game` draw the same secret and
+/// lies for the same setup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setup {
+    pub version: u32,
+    pub min: i128,
+    pub max: i128,
+    pub seed: u64,
+    pub max_attempts: Option<u32>,
+    pub time_limit_ms: Option<u64>,
+    pub max_lies: u32,
+}
+
+impl Setup {
+    pub fn new<N: Number>(range: GuessRange<N>, seed: u64) -> Self {
+        Setup {
+            version: VERSION,
+            min: range.min().to_i128(),
+            max: range.max().to_i128(),
+            seed,
+            max_attempts: None,
+            time_limit_ms: None,
+            max_lies: 0,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_time_limit(mut self, time_limit: Option<Duration>) -> Self {
+        self.time_limit_ms = time_limit.map(|limit| u64::try_from(limit.as_millis()).unwrap_or(u64::MAX));
+        self
+    }
+
+    pub fn with_lies(mut self, max_lies: u32) -> Self {
+        self.max_lies = max_lies;
+        self
+    }
+
+    /// The secret comes first from the seeded generator, then the lie seed.
+    /// Fails if the range doesn't fit `N`.
+    pub fn game<N: Number>(&self) -> Result<Game<N>, String> {
+        if self.version != VERSION {
+            return Err(format!("unsupported log version {}", self.version));
+        }
+        let number = |n: i128| N::from_i128(n).ok_or_else(|| format!("{n} does not fit this game"));
+        let range = GuessRange::new(number(self.min)?, number(self.max)?).map_err(|err| err.to_string())?;
+        let mut rng = rng::seeded(self.seed);
+        Ok(Game::in_range(range, &mut rng)
+            .with_max_attempts(self.max_attempts)
+            .with_time_limit(self.time_limit_ms.map(Duration::from_millis))
+            .with_lies(self.max_lies, rng.next_u64()))
+    }
+}
+
+/// One line of a log. Times are milliseconds since the game started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Record {
+    Start(Setup),
+    Guess { at_ms: u64, guess: i128, outcome: Outcome },
+    Hint { at_ms: u64, hint: Hint, clue: String },
+    End { at_ms: u64, status: Status, attempts: u32, score: u32, secret: i128 },
+}
+
+impl Record {
+    pub fn at_ms(&self) -> u64 {
+        match self {
+            Record::Start(_) => 0,
+            Record::Guess { at_ms, .. } | Record::Hint { at_ms, .. } | Record::End { at_ms, .. } => *at_ms,
+        }
+    }
+}
+
+/// A log read back from disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Log {
+    pub setup: Setup,
+    pub records: Vec<Record>,  // everything after `start`
+}
+
+impl Log {
+    /// Blank lines are skipped; the first record must be `start`.
+    pub fn read(input: impl BufRead) -> io::Result<Self> {
+        let invalid = |line: usize, err: &dyn std::fmt::Display| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: {err}"))
+        };
+        let mut setup = None;
+        let mut records = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line).map_err(|err| invalid(i + 1, &err))?;
+            match (record, &setup) {
+                (Record::Start(start), None) => setup = Some(start),
+                (Record::Start(_), Some(_)) => return Err(invalid(i + 1, &"a second start record")),
+                (_, None) => return Err(invalid(i + 1, &"the log must begin with a start record")),
+                (record, Some(_)) => records.push(record),
+            }
+        }
+        let setup = setup.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "the log is empty"))?;
+        Ok(Log { setup, records })
+    }
+
+    /// Whether the log goes on to the `end` record.
+    pub fn is_complete(&self) -> bool {
+        matches!(self.records.last(), Some(Record::End { .. }))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read(io::BufReader::new(File::open(path)?))
+    }
+
+    /// Replays the log through the engine and fails at the first record the
+    /// engine disagrees with. A log that stops before `end` is fine as far as
+    /// it goes.
+    pub fn verify<N: Number>(&self) -> Result<(), String> {
+        let mut game = self.setup.game::<N>()?;
+        let mut ended = false;
+        for record in &self.records {
+            if ended {
+                return Err("records after the end of the game".to_string());
+            }
+            match record {
+                Record::Start(_) => return Err("a second start record".to_string()),
+                Record::Guess { guess, outcome, .. } => {
+                    if game.is_over() {
+                        return Err(format!("guess {guess} after the game was over"));
+                    }
+                    let n = N::from_i128(*guess)
+                        .filter(|&n| game.range().contains(n))
+                        .ok_or_else(|| format!("guess {guess} is outside {}", game.range()))?;
+                    let actual = game.guess(n);
+                    if actual != *outcome {
+                        return Err(format!(
+                            "guess {} ({guess}): the log says {}, the game says {}",
+                            game.attempts(), outcome.describe(), actual.describe()
+                        ));
+                    }
+                }
+                Record::Hint { hint, clue, .. } => match game.hint(*hint) {
+                    Ok((actual, _)) if actual.to_string() == *clue => {}
+                    Ok((actual, _)) => return Err(format!("{} hint: the log says {clue}, the game says {actual}", hint.name())),
+                    Err(err) => return Err(format!("{} hint: {err}", hint.name())),
+                },
+                Record::End { at_ms, status, attempts, score, secret } => {
+                    ended = true;
+                    match status {
+                        Status::GaveUp => game.give_up(),
+                        Status::OutOfTime => match self.setup.time_limit_ms {
+                            Some(limit) if *at_ms >= limit => {}
+                            Some(_) => return Err("the game ran out of time before the limit".to_string()),
+                            None => return Err("the game ran out of time without a time limit".to_string()),
+                        },
+                        _ => {}
+                    }
+                    // Replays have no clock, so a countdown can't end them.
+                    let actual = match game.status() {
+                        Status::Playing if *status == Status::OutOfTime => Status::OutOfTime,
+                        actual => actual,
+                    };
+                    if actual != *status {
+                        return Err(format!("the log says the game ended {status:?}, the game says {actual:?}"));
+                    }
+                    if (game.attempts(), game.score()) != (*attempts, *score) {
+                        return Err(format!(
+                            "the log says {attempts} attempts for {score} points, the game says {} for {}",
+                            game.attempts(), game.score()
+                        ));
+                    }
+                    if game.secret().to_i128() != *secret {
+                        return Err(format!("the log says the number was {secret}, the game says {}", game.secret()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How `play_back` paces the records.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pace {
+    /// The original gaps, sped up by this factor; 0 doesn't wait at all.
+    Timed(f64),
+    /// Waits for a line of input before each record.
+    Step,
+}
+
+/// Prints the log as it happened: `[0:01.234] 50: too high`.
+pub fn play_back(log: &Log, mut input: impl BufRead, mut output: impl Write, pace: Pace) -> io::Result<()> {
+    let setup = &log.setup;
+    write!(output, "Replaying a game on {} to {}, seed {}", setup.min, setup.max, setup.seed)?;
+    match setup.max_lies {
+        0 => {}
+        1 => write!(output, ", up to 1 lie")?,
+        lies => write!(output, ", up to {lies} lies")?,
+    }
+    writeln!(output, ".")?;
+
+    let mut last_ms = 0;
+    for record in &log.records {
+        match pace {
+            Pace::Timed(speed) if speed > 0.0 => {
+                let gap = record.at_ms().saturating_sub(last_ms);
+                thread::sleep(Duration::from_millis(gap).div_f64(speed));
+            }
+            Pace::Timed(_) => {}
+            Pace::Step => {
+                output.flush()?;
+                if input.read_line(&mut String::new())? == 0 {
+                    return Ok(());
+                }
+            }
+        }
+        last_ms = record.at_ms();
+
+        let at_ms = record.at_ms();
+        let stamp = format!("[{}:{:02}.{:03}]", at_ms / 60_000, at_ms / 1000 % 60, at_ms % 1000);
+        match record {
+            Record::Start(_) => {}
+            Record::Guess { guess, outcome, .. } => writeln!(output, "{stamp} {guess}: {}", outcome.describe())?,
+            Record::Hint { hint, clue, .. } => writeln!(output, "{stamp} {} hint: {clue}", hint.name())?,
+            Record::End { status, attempts, secret, .. } => {
+                let ending = match status {
+                    Status::Won => "Won",
+                    Status::Lost => "Out of attempts",
+                    Status::OutOfTime => "Out of time",
+                    Status::GaveUp => "Gave up",
+                    Status::Playing => "Stopped",
+                };
+                writeln!(output, "{stamp} {ending} after {attempts} attempts; the number was {secret}.")?;
+            }
+        }
+    }
+    output.flush()
+}
+
+/// Writes a log as a game is played; hook it up with `observer`.
+pub struct Recorder {
+    log: Arc<Mutex<Writer>>,
+}
+
+struct Writer {
+    file: BufWriter<File>,
+    started: Instant,
+    error: Option<io::Error>,  // the first failed write; later records are dropped
+}
+
+impl Recorder {
+    /// Creates the file and writes the `start` record. Times count from
+    /// here, so create it before the game's clock starts: then no record is
+    /// stamped earlier than the game saw it.
+    pub fn create(path: &Path, setup: &Setup) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", serde_json::to_string(&Record::Start(setup.clone()))?)?;
+        file.flush()?;
+        let writer = Writer { file, started: Instant::now(), error: None };
+        Ok(Recorder { log: Arc::new(Mutex::new(writer)) })
+    }
+
+    /// Records guesses, hints and the end.
+    pub fn observer(&self) -> impl FnMut(&GameEvent) + Send + 'static {
+        let log = Arc::clone(&self.log);
+        move |event| {
+            let mut writer = log.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            writer.record(event);
+        }
+    }
+
+    /// Reports the first write that failed, if any.
+    pub fn finish(self) -> io::Result<()> {
+        let mut writer = self.log.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match writer.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Writer {
+    fn record(&mut self, event: &GameEvent) {
+        let at_ms = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let record = match *event {
+            GameEvent::Created { .. } | GameEvent::Guess { .. } => return,  // the feedback carries the guess
+            GameEvent::Feedback { guess, outcome } => Record::Guess { at_ms, guess, outcome },
+            GameEvent::Hint { hint, ref clue, .. } => Record::Hint { at_ms, hint, clue: clue.clone() },
+            GameEvent::Won { attempts, score, secret } => {
+                Record::End { at_ms, status: Status::Won, attempts, score, secret }
+            }
+            GameEvent::Lost { attempts, secret, reason } => {
+                let status = match reason {
+                    LossReason::OutOfAttempts => Status::Lost,
+                    LossReason::OutOfTime => Status::OutOfTime,
+                    LossReason::GaveUp => Status::GaveUp,
+                };
+                Record::End { at_ms, status, attempts, score: 0, secret }
+            }
+        };
+        if self.error.is_some() {
+            return;
+        }
+        let written = serde_json::to_string(&record)
+            .map_err(io::Error::from)
+            .and_then(|line| writeln!(self.file, "{line}"))
+            .and_then(|()| self.file.flush());
+        if let Err(err) = written {
+            self.error = Some(err);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display};
+use crate::range::{GuessRange, Number};
+
+/// Answers that contradict each other (or the range), i.e. the player cheated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inconsistent<N = u32> {
+    pub too_low: Option<N>,
+    pub too_high: Option<N>,
+    pub range: GuessRange<N>,
+}
+
+impl<N: Number> Display for Inconsistent<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.too_low, self.too_high) {
+            (Some(low), Some(high)) => write!(f, "you said {low} was too low but {high} was too high"),
+            (Some(low), None) => write!(f, "you said {low} was too low but the numbers stop at {}", self.range.max()),
+            (None, Some(high)) => write!(f, "you said {high} was too high but the numbers start at {}", self.range.min()),
+            (None, None) => write!(f, "your answers contradict each other"),
+        }
+    }
+}
+
+impl<N: Number> Error for Inconsistent<N> {}
+
+/// Binary-search player for a secret held by someone else. Feedback uses the
+/// same `Ordering` as the engine: `guess.cmp(&secret)`.
+#[derive(Debug, Clone)]
+pub struct Solver<N = u32> {
+    range: GuessRange<N>,
+    lo: i128,  // remaining candidates, inclusive
+    hi: i128,
+    too_low: Option<N>,
+    too_high: Option<N>,
+    attempts: u32,
+    found: Option<N>,
+}
+
+impl<N: Number> Solver<N> {
+    pub fn new(range: GuessRange<N>) -> Self {
+        Solver {
+            range,
+            lo: range.min().to_i128(),
+            hi: range.max().to_i128(),
+            too_low: None,
+            too_high: None,
+            attempts: 0,
+            found: None,
+        }
+    }
+
+    /// Middle of the remaining candidates, or `None` once solved.
+    pub fn next_guess(&self) -> Option<N> {
+        if self.found.is_some() {
+            return None;
+        }
+        N::from_i128(self.lo + (self.hi - self.lo) / 2)
+    }
+
+    /// Records the answer for `guess` and narrows the candidates.
+    pub fn answer(&mut self, guess: N, ordering: Ordering) -> Result<(), Inconsistent<N>> {
+        self.attempts += 1;
+        match ordering {
+            Ordering::Less => {
+                self.lo = self.lo.max(guess.to_i128() + 1);
+                self.too_low = Some(self.too_low.map_or(guess, |low| low.max(guess)));
+            }
+            Ordering::Greater => {
+                self.hi = self.hi.min(guess.to_i128() - 1);
+                self.too_high = Some(self.too_high.map_or(guess, |high| high.min(guess)));
+            }
+            Ordering::Equal => {
+                if !(self.lo..=self.hi).contains(&guess.to_i128()) {
+                    return Err(self.inconsistency());
+                }
+                self.found = Some(guess);
+            }
+        }
+        if self.lo > self.hi {
+            return Err(self.inconsistency());
+        }
+        Ok(())
+    }
+
+    fn inconsistency(&self) -> Inconsistent<N> {
+        Inconsistent { too_low: self.too_low, too_high: self.too_high, range: self.range }
+    }
+
+    pub fn range(&self) -> GuessRange<N> {
+        self.range
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn found(&self) -> Option<N> {
+        self.found
+    }
+
+    /// How many numbers are still possible.
+    pub fn remaining(&self) -> u128 {
+        (self.hi - self.lo + 1).max(0) as u128
+    }
+}
+
+/// Reads a player's reply: low/high/correct or their first letters.
+pub fn parse_answer(input: &str) -> Option<Ordering> {
+    match input.trim().to_ascii_lowercase().as_str() {
+        "l" | "low" | "too low" | "<" => Some(Ordering::Less),
+        "h" | "high" | "too high" | ">" => Some(Ordering::Greater),
+        "c" | "correct" | "yes" | "y" | "=" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// How a reverse game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict<N = u32> {
+    Solved { secret: N, attempts: u32 },
+    Cheating(Inconsistent<N>),
+    GaveUp,
+}
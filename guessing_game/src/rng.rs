@@ -0,0 +1,10 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Portable, seedable generator: the same seed yields the same secrets on
+/// every platform and release, unlike `thread_rng()` or `StdRng`.
+pub type SeededRng = ChaCha8Rng;
+
+pub fn seeded(seed: u64) -> SeededRng {
+    SeededRng::seed_from_u64(seed)
+}
@@ -0,0 +1,216 @@
+//! Saved games: enough to rebuild an unfinished `Game` move by move.
+//!
+//! The secret is stored XORed with a keystream drawn from a per-save nonce.
+//! That keeps it from being read at a glance; it is not encryption.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use crate::hint::Hint;
+use crate::{feedback, rng, stats, Game, GuessRange, Number};
+
+/// Bumped when the format changes incompatibly.
+pub const VERSION: u32 = 1;
+
+/// Mixed into the nonce so the keystream isn't just `seeded(nonce)`.
+const KEY_SALT: u64 = 0x5ec2_e70b_f05c_a7ed;
+
+/// One thing the player did, in order: `{"guess": 50}` or `{"hint": "parity"}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Move {
+    Guess(i128),
+    Hint(Hint),
+}
+
+/// A game as written to disk. Numbers are `i128` so one format covers every
+/// `Number` type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub min: i128,
+    pub max: i128,
+    secret: String,  // obfuscated, see `reveal`
+    nonce: u64,
+    pub seed: Option<u64>,  // the one the secret was drawn with, if any
+    pub max_attempts: Option<u32>,
+    pub time_limit_ms: Option<u64>,
+    pub elapsed_ms: u64,
+    pub max_lies: u32,
+    pub lie_seed: Option<u64>,
+    /// How misses are described; resuming keeps it, since low/high answers
+    /// would give away what the others hide.
+    #[serde(default = "default_feedback")]
+    pub feedback: String,
+    pub moves: Vec<Move>,
+}
+
+impl SavedGame {
+    pub fn capture<N: Number>(game: &Game<N>, seed: Option<u64>) -> Self {
+        let nonce = rand::thread_rng().next_u64();
+        let mut hints = game.hint_log().peekable();
+        let mut moves = Vec::new();
+        for (i, &(guess, _)) in game.history().iter().enumerate() {
+            while let Some((_, hint)) = hints.next_if(|&(after, _)| after as usize <= i) {
+                moves.push(Move::Hint(hint));
+            }
+            moves.push(Move::Guess(guess.to_i128()));
+        }
+        moves.extend(hints.map(|(_, hint)| Move::Hint(hint)));
+
+        SavedGame {
+            version: VERSION,
+            min: game.range().min().to_i128(),
+            max: game.range().max().to_i128(),
+            secret: format!("{:032x}", game.secret().to_i128() as u128 ^ keystream(nonce)),
+            nonce,
+            seed,
+            max_attempts: game.max_attempts(),
+            time_limit_ms: game.time_limit().map(millis),
+            elapsed_ms: millis(game.elapsed()),
+            max_lies: game.max_lies(),
+            lie_seed: game.lie_seed(),
+            feedback: default_feedback(),
+            moves,
+        }
+    }
+
+    /// Names the feedback model the game is played with.
+    pub fn with_feedback(mut self, name: &str) -> Self {
+        self.feedback = name.to_string();
+        self
+    }
+
+    /// Rebuilds the game by replaying its moves. Fails if the file was
+    /// tampered with, the numbers don't fit `N`, or the game is already over.
+    pub fn restore<N: Number>(&self) -> Result<Game<N>, String> {
+        if self.version != VERSION {
+            return Err(format!("unsupported save version {}", self.version));
+        }
+        let number = |n: i128| N::from_i128(n).ok_or_else(|| format!("{n} does not fit this game"));
+        let range = GuessRange::new(number(self.min)?, number(self.max)?).map_err(|err| err.to_string())?;
+        let secret = number(self.reveal()?)?;
+        if !range.contains(secret) {
+            return Err("the saved secret is corrupt".to_string());
+        }
+        if !feedback::NAMES.contains(&self.feedback.as_str()) {
+            return Err(format!("unknown feedback `{}` in save", self.feedback));
+        }
+
+        let mut game = Game::with_secret_in(range, secret)
+            .with_max_attempts(self.max_attempts)
+            .with_time_limit(self.time_limit_ms.map(Duration::from_millis))
+            .with_elapsed(Duration::from_millis(self.elapsed_ms));
+        if let Some(seed) = self.lie_seed {
+            game = game.with_lies(self.max_lies, seed);
+        }
+        for &step in &self.moves {
+            match step {
+                Move::Guess(guess) => {
+                    game.guess(number(guess)?);
+                }
+                Move::Hint(hint) => {
+                    game.hint(hint).map_err(|err| format!("bad hint in save: {err}"))?;
+                }
+            }
+        }
+        if game.is_over() {
+            return Err("the saved game is already over".to_string());
+        }
+        Ok(game)
+    }
+
+    fn reveal(&self) -> Result<i128, String> {
+        let masked = u128::from_str_radix(&self.secret, 16).map_err(|_| "the saved secret is corrupt".to_string())?;
+        Ok((masked ^ keystream(self.nonce)) as i128)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+}
+
+/// Where a game in progress is saved; frontends write to it on `save`,
+/// end of input or Ctrl-C.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSlot {
+    pub path: PathBuf,
+    pub seed: Option<u64>,
+    pub feedback: String,
+}
+
+impl SaveSlot {
+    /// A fresh file in `dir`, named after the current time, for a game with
+    /// low-high feedback.
+    pub fn new_in(dir: &Path, seed: Option<u64>) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        SaveSlot { path: dir.join(format!("{}.json", now.as_millis())), seed, feedback: default_feedback() }
+    }
+
+    pub fn with_feedback(mut self, name: &str) -> Self {
+        self.feedback = name.to_string();
+        self
+    }
+
+    pub fn write<N: Number>(&self, game: &Game<N>) -> io::Result<()> {
+        SavedGame::capture(game, self.seed).with_feedback(&self.feedback).save(&self.path)
+    }
+
+    /// Deletes the file once its game is over; a missing file is fine.
+    pub fn discard(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `saves/` in the stats data directory.
+pub fn default_dir() -> Option<PathBuf> {
+    Some(stats::data_dir()?.join("saves"))
+}
+
+/// The most recently written save in `dir`. Finished games delete their
+/// saves, so this is the latest unfinished game.
+pub fn latest(dir: &Path) -> io::Result<Option<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let modified = fs::metadata(&path)?.modified()?;
+            if newest.as_ref().is_none_or(|(time, _)| modified > *time) {
+                newest = Some((modified, path));
+            }
+        }
+    }
+    Ok(newest.map(|(_, path)| path))
+}
+
+fn default_feedback() -> String {
+    feedback::NAMES[0].to_string()  // saves from before it was recorded
+}
+
+fn keystream(nonce: u64) -> u128 {
+    rng::seeded(nonce ^ KEY_SALT).r#gen()
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
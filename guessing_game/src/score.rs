@@ -0,0 +1,13 @@
+/// Worst-case number of guesses binary search needs for `size` values.
+pub fn optimal_attempts(size: u128) -> u32 {
+    // ceil(log2(size + 1)), exact for any size
+    u128::BITS - size.leading_zeros()
+}
+
+/// Points for a win: bigger ranges are worth more (100 per bit of
+/// information), scaled down when play falls short of binary search.
+pub fn score(size: u128, attempts: u32) -> u32 {
+    let base = 100.0 * (size as f64).log2();
+    let efficiency = (optimal_attempts(size) as f64 / attempts.max(1) as f64).min(1.0);
+    (base * efficiency).round() as u32
+}
@@ -0,0 +1,516 @@
+use std::io::{self, BufRead, Write};
+use crate::bot::{BotError, ExternalBot};
+use crate::code::CodeGame;
+use crate::events::{self, GameEvent, Observer};
+use crate::feedback::{Feedback, LowHigh};
+use crate::hint::{self, Hint};
+use crate::hotseat::{HotSeat, Variant};
+use crate::reverse::{self, Solver, Verdict};
+use crate::save::SaveSlot;
+use crate::tournament::{Player, Standing, Tournament};
+use crate::word::{self, WordGame};
+use crate::{rng, strategy, Game, GuessError, GuessRange, Number, Outcome, Status};
+
+/// Drives a `Game` over any line-based reader and writer: stdin/stdout,
+/// in-memory buffers, files, pipes or sockets.
+pub struct Session<R, W> {
+    input: R,
+    output: W,
+    observers: Vec<Observer>,
+    feedback: Box<dyn Feedback>,
+    stopwatch: bool,
+    save_slot: Option<SaveSlot>,
+}
+
+/// ANSI: erase the screen and move the cursor home.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+impl<R: BufRead, W: Write> Session<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Session { input, output, observers: Vec::new(), feedback: Box::new(LowHigh), stopwatch: false, save_slot: None }
+    }
+
+    /// Calls `observer` with every event of games played through `play`.
+    pub fn with_observer(mut self, observer: impl FnMut(&GameEvent) + Send + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// How misses are described in `play`; too low/too high by default.
+    pub fn with_feedback(mut self, feedback: Box<dyn Feedback>) -> Self {
+        self.feedback = feedback;
+        self
+    }
+
+    /// Speedrun display: the clock time after every guess and in the summary.
+    pub fn with_stopwatch(mut self) -> Self {
+        self.stopwatch = true;
+        self
+    }
+
+    /// Enables `save` at the prompt and saves instead of giving up at the end
+    /// of input, once anything has been played; the file is deleted once the
+    /// game is over.
+    pub fn with_save_slot(mut self, slot: SaveSlot) -> Self {
+        self.save_slot = Some(slot);
+        self
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        events::notify(&mut self.observers, event);
+    }
+
+    /// Plays until the game is won, lost, or the player gives up with EOF.
+    /// Only I/O failures are returned; bad guesses are reported and re-prompted.
+    /// Starts the game's clock; with a time limit, a read that fails with
+    /// `ErrorKind::TimedOut` (see `input::TimedReader`) ends the game.
+    ///
+    /// `hint <name>` at the prompt asks for a hint instead of guessing. With a
+    /// save slot, `save` saves the game, and EOF saves it instead of giving up.
+    pub fn play<N: Number>(&mut self, game: &mut Game<N>) -> Result<(), GuessError<N>> {
+        writeln!(self.output, "Guess the number ({})!", game.range())?;
+        if let Some(limit) = game.time_limit() {
+            writeln!(self.output, "You have {} seconds.", limit.as_secs_f64())?;
+        }
+        if game.max_lies() > 0 {
+            writeln!(self.output, "Careful: I may lie up to {} times about low and high.", game.max_lies())?;
+        }
+        // Replaying the misses through the feedback model also picks up its
+        // state, e.g. the last distance for warmer/colder.
+        self.feedback.start(game.range().min().to_i128(), game.range().max().to_i128());
+        if !game.history().is_empty() {
+            let secret = game.secret().to_i128();
+            let guesses: Vec<String> = game.history().iter().map(|&(guess, outcome)| {
+                format!("{guess} ({})", self.feedback.miss(guess.to_i128(), secret, outcome))
+            }).collect();
+            writeln!(self.output, "Guesses so far: {}", guesses.join(", "))?;
+        }
+        self.output.flush()?;
+        self.emit(GameEvent::created(game));
+        game.start_clock();
+
+        while !game.is_over() {
+            let line = self.read_line();
+            game.check_time();  // a guess typed after the deadline doesn't count
+            if game.is_over() {
+                break;
+            }
+            let Some(line) = line? else {
+                if let Some(slot) = &self.save_slot
+                    && game.total_attempts() > 0  // nothing worth keeping otherwise
+                {
+                    slot.write(game)?;
+                    writeln!(self.output, "\nGame saved to {}; pick it up with --continue.", slot.path.display())?;
+                    return Ok(self.output.flush()?);
+                }
+                game.give_up();
+                break;
+            };
+            if let Some(slot) = self.save_slot.as_ref().filter(|_| line.trim().eq_ignore_ascii_case("save")) {
+                slot.write(game)?;
+                writeln!(self.output, "Game saved to {}.", slot.path.display())?;
+                continue;
+            }
+            if let Some(request) = hint::parse_request(&line) {
+                self.hint(game, request)?;
+                continue;
+            }
+            let guess = match game.range().parse(&line) {
+                Ok(num) => num,
+                Err(err) => {
+                    writeln!(self.output, "{err}, try again:")?;
+                    continue;
+                }
+            };
+
+            let feedback = match game.guess(guess) {
+                Outcome::Win => "You win!".to_string(),
+                outcome => self.feedback.miss(guess.to_i128(), game.secret().to_i128(), outcome),
+            };
+            GameEvent::last_guess(game).into_iter().flatten().for_each(|event| self.emit(event));
+            let mut notes = Vec::new();
+            if let Some(left) = game.attempts_left().filter(|_| !game.is_over()) {
+                notes.push(format!("{left} left"));
+            }
+            if let Some(left) = game.time_left().filter(|_| !game.is_over()) {
+                notes.push(format!("{}s to go", left.as_secs()));
+            }
+            if self.stopwatch {
+                notes.push(format!("{:.3}s", game.elapsed().as_secs_f64()));
+            }
+            if notes.is_empty() {
+                writeln!(self.output, "{feedback}")?;
+            } else {
+                writeln!(self.output, "{feedback} ({})", notes.join(", "))?;
+            }
+            self.output.flush()?;
+        }
+
+        if let Some(event) = GameEvent::finished(game) {
+            self.emit(event);
+        }
+        if let Some(slot) = &self.save_slot {
+            slot.discard()?;
+        }
+        self.summary(game)?;
+        Ok(self.output.flush()?)
+    }
+
+    fn hint<N: Number>(&mut self, game: &mut Game<N>, request: Result<Hint, String>) -> io::Result<()> {
+        let hint = match request {
+            Ok(hint) => hint,
+            Err(usage) => return writeln!(self.output, "{usage}"),
+        };
+        match game.hint(hint) {
+            Ok((clue, new)) => {
+                if new {
+                    self.emit(GameEvent::hint(game, hint, &clue));
+                }
+                writeln!(self.output, "Hint: {clue} (+{} attempts so far).", game.penalty())
+            }
+            Err(err) => writeln!(self.output, "No hint: {err}."),
+        }
+    }
+
+    fn summary<N: Number>(&mut self, game: &Game<N>) -> Result<(), GuessError<N>> {
+        match game.status() {
+            Status::Lost => writeln!(self.output, "Out of attempts, the number was {}.", game.secret())?,
+            Status::OutOfTime => writeln!(self.output, "Time's up, the number was {}.", game.secret())?,
+            Status::GaveUp => writeln!(self.output, "You gave up, the number was {}.", game.secret())?,
+            Status::Won | Status::Playing => {}
+        }
+        if game.max_lies() > 0 {
+            let lies: Vec<String> = game.lies().iter().map(|&i| format!("{} ({})", i + 1, game.history()[i].0)).collect();
+            match lies.as_slice() {
+                [] => writeln!(self.output, "I never lied.")?,
+                _ => writeln!(self.output, "I lied about guesses {}.", lies.join(", "))?,
+            }
+        }
+        match game.penalty() {
+            0 => writeln!(self.output, "Attempts: {}, score: {}", game.attempts(), game.score())?,
+            penalty => writeln!(self.output, "Attempts: {} + {penalty} for hints, score: {}", game.attempts(), game.score())?,
+        }
+        if self.stopwatch && game.status() == Status::Won {
+            writeln!(self.output, "Time: {:.3}s", game.elapsed().as_secs_f64())?;
+        }
+        Ok(())
+    }
+
+    /// Reads one line; `None` means the input is exhausted.
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        Ok((self.input.read_line(&mut line)? > 0).then_some(line))
+    }
+
+    fn read_guess<N: Number>(&mut self, game: &Game<N>) -> Result<Option<N>, GuessError<N>> {
+        match self.read_line()? {
+            Some(line) => game.range().parse(&line).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Bulls and Cows: the same read, parse, compare, report loop as `play`,
+    /// over digit codes.
+    pub fn play_code(&mut self, game: &mut CodeGame) -> io::Result<()> {
+        writeln!(self.output, "Crack the code ({})!", game.rules())?;
+
+        while !game.is_over() {
+            let Some(line) = self.read_line()? else {
+                game.give_up();
+                break;
+            };
+            let guess = match game.rules().parse(&line) {
+                Ok(code) => code,
+                Err(err) => {
+                    writeln!(self.output, "{err}, try again:")?;
+                    continue;
+                }
+            };
+
+            let score = game.guess(&guess);
+            match game.attempts_left() {
+                _ if game.status() == Status::Won => writeln!(self.output, "You cracked it!")?,
+                Some(left) if !game.is_over() => writeln!(self.output, "{score} ({left} left)")?,
+                _ => writeln!(self.output, "{score}")?,
+            }
+        }
+
+        match game.status() {
+            Status::Lost => writeln!(self.output, "Out of attempts, the code was {}.", game.secret())?,
+            Status::GaveUp => writeln!(self.output, "You gave up, the code was {}.", game.secret())?,
+            _ => {}
+        }
+        writeln!(self.output, "Attempts: {}", game.attempts())?;
+        self.output.flush()
+    }
+
+    /// Word mode: the same loop again, with each guess marked letter by letter.
+    pub fn play_words(&mut self, game: &mut WordGame) -> io::Result<()> {
+        let length = game.dictionary().length();
+        let hard = if game.is_hard() { " (hard mode)" } else { "" };
+        writeln!(self.output, "Guess the {length}-letter word{hard}!")?;
+        writeln!(self.output, "+ right place, ? wrong place, - not in the word")?;
+
+        while !game.is_over() {
+            let Some(line) = self.read_line()? else {
+                game.give_up();
+                break;
+            };
+            let guess = match game.parse(&line) {
+                Ok(word) => word,
+                Err(err) => {
+                    writeln!(self.output, "{err}, try again:")?;
+                    continue;
+                }
+            };
+
+            let marks = word::pattern(&game.guess(&guess));
+            match game.attempts_left() {
+                Some(left) if !game.is_over() => writeln!(self.output, "{guess} {marks} ({left} left)")?,
+                _ => writeln!(self.output, "{guess} {marks}")?,
+            }
+        }
+
+        match game.status() {
+            Status::Won => writeln!(self.output, "You got it in {}!", game.attempts())?,
+            Status::Lost => writeln!(self.output, "Out of attempts, the word was {}.", game.secret())?,
+            Status::GaveUp => writeln!(self.output, "You gave up, the word was {}.", game.secret())?,
+            _ => {}
+        }
+        self.output.flush()
+    }
+
+    /// Hot-seat mode: players share the input and take turns.
+    pub fn play_hotseat<N: Number>(&mut self, hotseat: &mut HotSeat<N>) -> Result<(), GuessError<N>> {
+        let names: Vec<&str> = (0..hotseat.players()).map(|p| hotseat.name(p)).collect();
+        match hotseat.variant() {
+            Variant::Shared => writeln!(self.output, "{}: guess the number ({})!", names.join(", "), hotseat.range())?,
+            Variant::Race => writeln!(self.output, "{}: race to guess your own number ({})!", names.join(", "), hotseat.range())?,
+        }
+
+        while !hotseat.is_over() {
+            let player = hotseat.turn();
+            let game = hotseat.game(player);
+            writeln!(self.output, "{}'s turn (attempt {}):", hotseat.name(player), game.attempts() + 1)?;
+
+            let guess = match self.read_guess(game) {
+                Ok(Some(num)) => num,
+                Ok(None) => {
+                    hotseat.give_up();
+                    break;
+                }
+                Err(GuessError::Io(err)) => return Err(GuessError::Io(err)),
+                Err(err) => {
+                    writeln!(self.output, "{err}, try again.")?;
+                    continue;
+                }
+            };
+
+            match hotseat.guess(guess) {
+                Outcome::Low => writeln!(self.output, "Too low!")?,
+                Outcome::High => writeln!(self.output, "Too high!")?,
+                Outcome::Win => writeln!(self.output, "{} found it!", hotseat.name(player))?,
+            }
+            if hotseat.game(player).status() == Status::Lost {
+                writeln!(self.output, "{} is out of attempts.", hotseat.name(player))?;
+            }
+        }
+
+        if hotseat.variant() == Variant::Shared {
+            writeln!(self.output, "The number was {}.", hotseat.game(0).secret())?;
+        }
+        for player in 0..hotseat.players() {
+            let game = hotseat.game(player);
+            let result = match game.status() {
+                Status::Won => "found it",
+                Status::Lost => "out of attempts",
+                Status::OutOfTime => "out of time",
+                Status::GaveUp => "gave up",
+                Status::Playing => "still guessing",
+            };
+            write!(self.output, "{}: {} attempts, {result}", hotseat.name(player), game.attempts())?;
+            match hotseat.variant() {
+                Variant::Race => writeln!(self.output, " (number {})", game.secret())?,
+                Variant::Shared => writeln!(self.output)?,
+            }
+        }
+
+        let winners: Vec<&str> = hotseat.winners().into_iter().map(|p| hotseat.name(p)).collect();
+        match winners.as_slice() {
+            [] => writeln!(self.output, "Nobody wins.")?,
+            [winner] => writeln!(self.output, "{winner} wins!")?,
+            tied => writeln!(self.output, "Tie between {}!", tied.join(" and "))?,
+        }
+        Ok(self.output.flush()?)
+    }
+
+    /// Lets an external bot play `game`, narrating each move. A bot that
+    /// times out, exits or breaks the protocol forfeits: the game is given up
+    /// and the reason returned as `Ok(Some(_))`. `Err` is for output errors.
+    pub fn play_bot<N: Number>(&mut self, game: &mut Game<N>, bot: &mut ExternalBot) -> io::Result<Option<BotError>> {
+        writeln!(self.output, "{} is guessing the number ({}).", bot.command(), game.range())?;
+        self.emit(GameEvent::created(game));
+        game.start_clock();
+
+        let mut forfeit = bot.start(game).err();
+        while !game.is_over() && forfeit.is_none() {
+            let guess = match bot.next_guess(game) {
+                Ok(guess) => guess,
+                Err(err) => {
+                    forfeit = Some(err);
+                    break;
+                }
+            };
+            let outcome = game.guess(guess);
+            for event in GameEvent::last_guess(game).into_iter().flatten() {
+                self.emit(event);
+            }
+            writeln!(self.output, "{guess}: {}", outcome.describe())?;
+            if !game.is_over() {
+                forfeit = bot.feedback(guess, outcome).err();
+            }
+        }
+
+        if let Some(err) = &forfeit {
+            game.give_up();
+            writeln!(self.output, "The bot forfeits: {err}.")?;
+        }
+        bot.finish(game);
+        if let Some(event) = GameEvent::finished(game) {
+            self.emit(event);
+        }
+        match game.status() {
+            Status::Won => writeln!(self.output, "Found in {} attempts, score: {}", game.attempts(), game.score())?,
+            Status::Lost => writeln!(self.output, "Out of attempts, the number was {}.", game.secret())?,
+            _ => writeln!(self.output, "The number was {}.", game.secret())?,
+        }
+        self.output.flush()?;
+        Ok(forfeit)
+    }
+
+    /// Plays every match of a tournament. Humans play their side in turn
+    /// with `play`, and the screen is cleared between two humans sharing the
+    /// keyboard; bots play theirs instantly. Ends with the standings.
+    pub fn play_tournament<N: Number>(&mut self, tournament: &mut Tournament<N>) -> Result<(), GuessError<N>> {
+        writeln!(self.output, "{} tournament on {}, seed {}: {}.", tournament.format(), tournament.range(),
+                 tournament.seed(), tournament.entrants().iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))?;
+
+        while let Some(pairing) = tournament.next_match() {
+            let (home, away) = (tournament.entrant(pairing.home), tournament.entrant(pairing.away));
+            writeln!(self.output, "\nRound {}, match {}: {} vs {}", pairing.round, pairing.number, home.name, away.name)?;
+            let mut games = [tournament.game(&pairing), tournament.game(&pairing)];
+            let mut human_played = false;
+            for (entrant, game) in [home, away].into_iter().zip(&mut games) {
+                match &entrant.player {
+                    Player::Human => {
+                        if human_played {
+                            self.hand_off(&entrant.name)?;
+                        }
+                        human_played = true;
+                        writeln!(self.output, "{}, your turn.", entrant.name)?;
+                        self.play(game)?;
+                    }
+                    Player::Program(command) => {
+                        // A forfeit is a result here; `play_bot` reports it.
+                        match ExternalBot::spawn(command) {
+                            Ok(bot) => {
+                                self.play_bot(game, &mut bot.with_timeout(tournament.bot_timeout()))?;
+                            }
+                            Err(err) => {
+                                game.give_up();
+                                writeln!(self.output, "{} forfeits: {err}.", entrant.name)?;
+                            }
+                        }
+                    }
+                    Player::Bot(name) => {
+                        let mut bot = strategy::by_name(name).expect("entrants name known strategies");
+                        let mut moves = rng::seeded(pairing.seed.wrapping_add(1));
+                        let result = match strategy::play(bot.as_mut(), game, &mut moves) {
+                            Status::Won => "found it in",
+                            _ => "missed after",
+                        };
+                        writeln!(self.output, "{} {result} {} attempts.", entrant.name, game.attempts())?;
+                    }
+                }
+            }
+
+            let winner = tournament.record(&pairing, &games[0], &games[1]).winner;
+            match winner {
+                Some(winner) => writeln!(self.output, "{} wins the match.", tournament.entrant(winner).name)?,
+                None if tournament.next_match().is_some_and(|next| next.home == pairing.home && next.away == pairing.away) => {
+                    writeln!(self.output, "A draw; replaying on a new number.")?
+                }
+                None => writeln!(self.output, "A draw.")?,
+            }
+        }
+
+        writeln!(self.output, "\n{}", Standing::HEADER)?;
+        for standing in tournament.standings() {
+            writeln!(self.output, "{standing}")?;
+        }
+        match tournament.leaders().as_slice() {
+            [champion] => writeln!(self.output, "{} wins the tournament!", tournament.entrant(*champion).name)?,
+            leaders => {
+                let names: Vec<&str> = leaders.iter().map(|&e| tournament.entrant(e).name.as_str()).collect();
+                let (last, rest) = names.split_last().expect("a tournament has entrants");
+                writeln!(self.output, "{} and {last} share the tournament.", rest.join(", "))?
+            }
+        }
+        Ok(self.output.flush()?)
+    }
+
+    /// Waits for the next player to take the keyboard, then clears the
+    /// screen so they can't read the last player's game.
+    fn hand_off(&mut self, name: &str) -> io::Result<()> {
+        writeln!(self.output, "Pass the keyboard to {name} and press Enter.")?;
+        self.output.flush()?;
+        self.input.read_line(&mut String::new())?;
+        write!(self.output, "{CLEAR_SCREEN}")
+    }
+
+    /// Reverse mode: the player holds the secret and the program guesses.
+    pub fn play_reverse<N: Number>(&mut self, range: GuessRange<N>) -> io::Result<Verdict<N>> {
+        writeln!(self.output, "Think of a number ({range}) and I'll guess it.")?;
+        writeln!(self.output, "Answer low, high or correct (l/h/c).")?;
+
+        let mut solver = Solver::new(range);
+        let verdict = loop {
+            let guess = solver.next_guess().expect("solver stops guessing only once solved");
+            writeln!(self.output, "Is it {guess}?")?;
+
+            let ordering = loop {
+                let mut line = String::new();
+                if self.input.read_line(&mut line)? == 0 {
+                    break None;
+                }
+                match reverse::parse_answer(&line) {
+                    Some(ordering) => break Some(ordering),
+                    None => writeln!(self.output, "please answer low, high or correct, try again:")?,
+                }
+            };
+            let Some(ordering) = ordering else { break Verdict::GaveUp };
+
+            if let Err(cheat) = solver.answer(guess, ordering) {
+                break Verdict::Cheating(cheat);
+            }
+            if let Some(secret) = solver.found() {
+                break Verdict::Solved { secret, attempts: solver.attempts() };
+            }
+        };
+
+        match verdict {
+            Verdict::Solved { secret, attempts } => {
+                writeln!(self.output, "Got it: {secret} in {attempts} attempts!")?
+            }
+            Verdict::Cheating(cheat) => writeln!(self.output, "Cheater! {cheat}.")?,
+            Verdict::GaveUp => writeln!(self.output, "You gave up.")?,
+        }
+        self.output.flush()?;
+        Ok(verdict)
+    }
+
+    /// Hands back the reader and writer, e.g. to inspect an in-memory transcript.
+    pub fn into_inner(self) -> (R, W) {
+        (self.input, self.output)
+    }
+}
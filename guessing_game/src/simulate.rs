@@ -0,0 +1,82 @@
+use std::fmt::{self, Display};
+use rand::RngCore;
+use crate::strategy::{self, Strategy};
+use crate::{rng, Game, GuessRange, Number, Status};
+
+/// Aggregate results of many automatic games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub strategy: String,
+    pub games: u32,
+    pub wins: u32,
+    pub mean: f64,  // attempts, over wins only
+    pub median: u32,
+    pub p99: u32,
+    pub mean_score: f64,
+}
+
+impl Report {
+    pub const HEADER: &str = "strategy   games  win rate    mean  median   p99   score";
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 { 0.0 } else { self.wins as f64 / self.games as f64 }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<8} {:>7} {:>8.1}% {:>7.2} {:>7} {:>5} {:>7.1}",
+               self.strategy, self.games, 100.0 * self.win_rate(),
+               self.mean, self.median, self.p99, self.mean_score)
+    }
+}
+
+/// Plays `games` games, each with a lying oracle when `max_lies` > 0.
+/// Secrets (and lies) come from `seed` alone, so every strategy faces the
+/// same sequence; the strategy's own randomness is seeded apart.
+pub fn simulate<N: Number>(
+    strategy: &mut dyn Strategy,
+    range: GuessRange<N>,
+    max_attempts: Option<u32>,
+    max_lies: u32,
+    games: u32,
+    seed: u64,
+) -> Report {
+    let mut secrets = rng::seeded(seed);
+    let mut moves = rng::seeded(seed.wrapping_add(1));
+    let mut lies = rng::seeded(seed.wrapping_add(2));
+    let mut attempts = Vec::new();
+    let mut total_score = 0u64;
+
+    for _ in 0..games {
+        let mut game = Game::in_range(range, &mut secrets)
+            .with_max_attempts(max_attempts)
+            .with_lies(max_lies, lies.next_u64());
+        if strategy::play(strategy, &mut game, &mut moves as &mut dyn RngCore) == Status::Won {
+            attempts.push(game.attempts());
+            total_score += u64::from(game.score());
+        }
+    }
+
+    attempts.sort_unstable();
+    let wins = attempts.len() as u32;
+    let mean = if wins == 0 { 0.0 } else { attempts.iter().map(|&a| a as f64).sum::<f64>() / wins as f64 };
+    Report {
+        strategy: strategy.name().to_string(),
+        games,
+        wins,
+        mean,
+        median: percentile(&attempts, 0.5),
+        p99: percentile(&attempts, 0.99),
+        mean_score: if games == 0 { 0.0 } else { total_score as f64 / games as f64 },
+    }
+}
+
+/// Nearest-rank percentile of sorted values; 0 when empty.
+fn percentile(sorted: &[u32], p: f64) -> u32 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
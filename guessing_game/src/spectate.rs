@@ -0,0 +1,140 @@
+//! Live WebSocket feed of a game's events for spectators.
+//!
+//! Clients connect to `ws://<addr>/events` and receive one JSON text message
+//! per `GameEvent`; late joiners first get everything published so far.
+
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::{http, Message};
+use crate::events::GameEvent;
+
+pub const PATH: &str = "/events";
+
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// A connection that hasn't finished the handshake by then is dropped, so
+/// it can't hold up `finish`.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct Spectators {
+    addr: SocketAddr,
+    feed: Arc<Mutex<Feed>>,
+}
+
+#[derive(Default)]
+struct Feed {
+    history: Vec<String>,
+    subscribers: Vec<Sender<String>>,
+    closed: bool,
+    clients: Vec<JoinHandle<()>>,
+}
+
+impl Spectators {
+    /// Starts accepting spectators in the background.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let feed = Arc::new(Mutex::new(Feed::default()));
+
+        let accepting = Arc::clone(&feed);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let client_feed = Arc::clone(&accepting);
+                let client = thread::spawn(move || {
+                    let _ = serve(stream, &client_feed);  // a spectator leaving is not an error
+                });
+                lock(&accepting).clients.push(client);
+            }
+        });
+        Ok(Spectators { addr, feed })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn url(&self) -> String {
+        format!("ws://{}{PATH}", self.addr)
+    }
+
+    pub fn publish(&self, event: &GameEvent) {
+        publish(&self.feed, event);
+    }
+
+    /// A session observer that forwards to this feed.
+    pub fn observer(&self) -> impl FnMut(&GameEvent) + Send + 'static {
+        let feed = Arc::clone(&self.feed);
+        move |event| publish(&feed, event)
+    }
+
+    /// Ends the feed and waits for connected spectators to receive it all.
+    pub fn finish(self) {
+        let clients = {
+            let mut feed = lock(&self.feed);
+            feed.closed = true;
+            feed.subscribers.clear();
+            std::mem::take(&mut feed.clients)
+        };
+        for client in clients {
+            let _ = client.join();
+        }
+    }
+}
+
+fn publish(feed: &Mutex<Feed>, event: &GameEvent) {
+    let line = serde_json::to_string(event).expect("events serialize");
+    let mut feed = lock(feed);
+    feed.subscribers.retain(|subscriber| subscriber.send(line.clone()).is_ok());
+    feed.history.push(line);
+}
+
+#[allow(clippy::result_large_err)]  // the handshake callback's error type is tungstenite's
+fn serve(stream: TcpStream, feed: &Mutex<Feed>) -> tungstenite::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut socket = tungstenite::accept_hdr(stream, |request: &Request, response: Response| {
+        if request.uri().path() == PATH {
+            Ok(response)
+        } else {
+            Err(not_found())
+        }
+    })
+    .map_err(|err| match err {
+        tungstenite::HandshakeError::Failure(err) => err,
+        tungstenite::HandshakeError::Interrupted(_) => tungstenite::Error::ConnectionClosed,
+    })?;
+
+    // Subscribing under the lock means no event is missed or repeated
+    // between the replay and the live feed.
+    let (tx, rx) = mpsc::channel();
+    {
+        let mut feed = lock(feed);
+        for line in &feed.history {
+            let _ = tx.send(line.clone());
+        }
+        if !feed.closed {
+            feed.subscribers.push(tx);
+        }
+    }
+
+    for line in rx {
+        socket.send(Message::Text(line.into()))?;
+    }
+    socket.close(None)?;
+    socket.flush()
+}
+
+fn not_found() -> ErrorResponse {
+    http::Response::builder()
+        .status(http::StatusCode::NOT_FOUND)
+        .body(Some(format!("spectators connect to {PATH}")))
+        .expect("static response")
+}
+
+fn lock(feed: &Mutex<Feed>) -> MutexGuard<'_, Feed> {
+    feed.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::{Difficulty, Game, GuessRange, Number, Status};
+
+/// Overrides the directory the stats file lives in.
+pub const DATA_DIR_ENV: &str = "GUESSING_GAME_DATA_DIR";
+
+const HISTOGRAM_WIDTH: u32 = 40;
+
+/// Totals for one difficulty (or one custom range).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]  // files written by older versions lack newer fields
+pub struct Record {
+    pub played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub win_attempts: u64,  // summed over wins, for the average
+    pub best_attempts: Option<u32>,
+    pub best_score: u32,
+    pub attempts_to_win: BTreeMap<u32, u32>,  // attempts -> number of wins
+    pub best_time_ms: Option<u64>,  // fastest speedrun win
+}
+
+impl Record {
+    pub fn average_attempts(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.win_attempts as f64 / self.wins as f64)
+    }
+}
+
+/// Per-player history, persisted as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub by_difficulty: BTreeMap<String, Record>,
+}
+
+impl Stats {
+    /// `stats.json` in the `data_dir`.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(data_dir()?.join("stats.json"))
+    }
+
+    /// A missing file is an empty history.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Stats::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Adds a finished game; unfinished games are ignored.
+    pub fn record<N: Number>(&mut self, game: &Game<N>) {
+        if !game.is_over() {
+            return;
+        }
+        let record = self.by_difficulty.entry(category(game.range())).or_default();
+        record.played += 1;
+        if game.status() == Status::Won {
+            let attempts = game.total_attempts();  // hints count, as in the score
+            record.wins += 1;
+            record.win_attempts += u64::from(attempts);
+            record.best_attempts = Some(record.best_attempts.map_or(attempts, |best| best.min(attempts)));
+            record.best_score = record.best_score.max(game.score());
+            *record.attempts_to_win.entry(attempts).or_default() += 1;
+        } else {
+            record.losses += 1;
+        }
+    }
+
+    /// Records a speedrun's time if the game was won; returns whether it is
+    /// a new personal best for its difficulty.
+    pub fn record_speedrun<N: Number>(&mut self, game: &Game<N>) -> bool {
+        if game.status() != Status::Won {
+            return false;
+        }
+        let millis = u64::try_from(game.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let record = self.by_difficulty.entry(category(game.range())).or_default();
+        if record.best_time_ms.is_some_and(|best| best <= millis) {
+            return false;
+        }
+        record.best_time_ms = Some(millis);
+        true
+    }
+
+    /// Prints the summary table followed by one histogram per difficulty.
+    pub fn write_report<W: Write>(&self, mut out: W) -> io::Result<()> {
+        if self.by_difficulty.is_empty() {
+            return writeln!(out, "No games played yet.");
+        }
+
+        writeln!(out, "{:<16} {:>6} {:>5} {:>6} {:>8} {:>5} {:>6} {:>9}",
+                 "difficulty", "played", "wins", "losses", "avg", "best", "score", "fastest")?;
+        for (name, record) in &self.by_difficulty {
+            let avg = record.average_attempts().map_or("-".to_string(), |avg| format!("{avg:.2}"));
+            let best = record.best_attempts.map_or("-".to_string(), |best| best.to_string());
+            let fastest = record.best_time_ms.map_or("-".to_string(), format_millis);
+            writeln!(out, "{:<16} {:>6} {:>5} {:>6} {:>8} {:>5} {:>6} {:>9}",
+                     name, record.played, record.wins, record.losses, avg, best, record.best_score, fastest)?;
+        }
+
+        for (name, record) in &self.by_difficulty {
+            let Some(&most) = record.attempts_to_win.values().max() else { continue };
+            writeln!(out, "\n{name}: attempts to win")?;
+            for (attempts, &count) in &record.attempts_to_win {
+                let bar = (count * HISTOGRAM_WIDTH).div_ceil(most) as usize;
+                writeln!(out, "{attempts:>4} | {} {count}", "#".repeat(bar))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stats key for a range: the preset's name, or the range itself.
+pub fn category<N: Number>(range: GuessRange<N>) -> String {
+    Difficulty::ALL
+        .into_iter()
+        .find(|d| d.range::<N>() == range)
+        .map_or_else(|| range.to_string(), |d| d.name().to_string())
+}
+
+/// `12345` -> `12.345s`
+pub fn format_millis(millis: u64) -> String {
+    format!("{}.{:03}s", millis / 1000, millis % 1000)
+}
+
+/// `$GUESSING_GAME_DATA_DIR`, else `$XDG_DATA_HOME/guessing_game`, else
+/// `~/.local/share/guessing_game`.
+pub fn data_dir() -> Option<PathBuf> {
+    match env::var_os(DATA_DIR_ENV) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(data_home()?.join("guessing_game")),
+    }
+}
+
+fn data_home() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
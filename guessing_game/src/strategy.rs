@@ -0,0 +1,276 @@
+use rand::{Rng, RngCore};
+use crate::{Game, Number, Outcome, Status};
+
+/// An automatic player. Guesses are `i128` so one implementation covers
+/// every `Number` type; `play` converts at the edges.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// Called before `start` when the game may lie up to `max_lies` times.
+    /// Strategies that assume honest answers can ignore it.
+    fn expect_lies(&mut self, _max_lies: u32) {}
+
+    /// Called before each game with the inclusive range.
+    fn start(&mut self, min: i128, max: i128);
+
+    fn next_guess(&mut self, rng: &mut dyn RngCore) -> i128;
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome);
+}
+
+/// Names accepted by `by_name`, in the order reports list them.
+pub const NAMES: [&str; 6] = ["binary", "random", "linear", "golden", "human", "tolerant"];
+
+pub fn by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    let strategy: Box<dyn Strategy> = match name {
+        "binary" => Box::new(BinarySearch::default()),
+        "random" => Box::new(Random::default()),
+        "linear" => Box::new(LinearScan::default()),
+        "golden" => Box::new(GoldenSection::default()),
+        "human" => Box::new(HumanLike::default()),
+        "tolerant" => Box::new(LieTolerant::default()),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+/// Plays `game` to the end and returns how it finished.
+pub fn play<N: Number>(strategy: &mut dyn Strategy, game: &mut Game<N>, rng: &mut dyn RngCore) -> Status {
+    let range = game.range();
+    strategy.expect_lies(game.max_lies());
+    strategy.start(range.min().to_i128(), range.max().to_i128());
+    while !game.is_over() {
+        let guess = strategy.next_guess(rng);
+        let value = N::from_i128(guess).expect("strategies guess inside the range");
+        let outcome = game.guess(value);
+        strategy.feedback(guess, outcome);
+    }
+    game.status()
+}
+
+/// Candidates consistent with the feedback so far.
+#[derive(Debug, Clone, Copy, Default)]
+struct Interval {
+    lo: i128,
+    hi: i128,
+    min: i128,  // the whole range, to start over from
+    max: i128,
+}
+
+impl Interval {
+    fn new(min: i128, max: i128) -> Self {
+        Interval { lo: min, hi: max, min, max }
+    }
+
+    /// An empty interval means some answer was a lie: start over.
+    fn narrow(&mut self, guess: i128, outcome: Outcome) {
+        match outcome {
+            Outcome::Low => self.lo = self.lo.max(guess + 1),
+            Outcome::High => self.hi = self.hi.min(guess - 1),
+            Outcome::Win => (self.lo, self.hi) = (guess, guess),
+        }
+        if self.lo > self.hi {
+            (self.lo, self.hi) = (self.min, self.max);
+        }
+    }
+
+    /// Point `fraction` of the way from `lo` to `hi`.
+    fn at(&self, fraction: f64) -> i128 {
+        let offset = ((self.hi - self.lo) as f64 * fraction).round() as i128;
+        (self.lo + offset).clamp(self.lo, self.hi)
+    }
+}
+
+/// Always halves the candidates: optimal worst case.
+#[derive(Debug, Default)]
+pub struct BinarySearch(Interval);
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.0 = Interval::new(min, max);
+    }
+
+    fn next_guess(&mut self, _rng: &mut dyn RngCore) -> i128 {
+        self.0.lo + (self.0.hi - self.0.lo) / 2
+    }
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome) {
+        self.0.narrow(guess, outcome);
+    }
+}
+
+/// Picks uniformly among the remaining candidates.
+#[derive(Debug, Default)]
+pub struct Random(Interval);
+
+impl Strategy for Random {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.0 = Interval::new(min, max);
+    }
+
+    fn next_guess(&mut self, rng: &mut dyn RngCore) -> i128 {
+        rng.gen_range(self.0.lo..=self.0.hi)
+    }
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome) {
+        self.0.narrow(guess, outcome);
+    }
+}
+
+/// Counts up from the bottom: the worst sensible strategy.
+#[derive(Debug, Default)]
+pub struct LinearScan(Interval);
+
+impl Strategy for LinearScan {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.0 = Interval::new(min, max);
+    }
+
+    fn next_guess(&mut self, _rng: &mut dyn RngCore) -> i128 {
+        self.0.lo
+    }
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome) {
+        self.0.narrow(guess, outcome);
+    }
+}
+
+/// Splits at the golden ratio instead of the middle.
+#[derive(Debug, Default)]
+pub struct GoldenSection(Interval);
+
+impl Strategy for GoldenSection {
+    fn name(&self) -> &'static str {
+        "golden"
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.0 = Interval::new(min, max);
+    }
+
+    fn next_guess(&mut self, _rng: &mut dyn RngCore) -> i128 {
+        const INV_PHI_SQUARED: f64 = 0.381_966_011_250_105;
+        self.0.at(INV_PHI_SQUARED)
+    }
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome) {
+        self.0.narrow(guess, outcome);
+    }
+}
+
+/// Bisection with a shaky hand: aims for the middle but lands anywhere in
+/// its central half.
+#[derive(Debug, Default)]
+pub struct HumanLike(Interval);
+
+impl Strategy for HumanLike {
+    fn name(&self) -> &'static str {
+        "human"
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.0 = Interval::new(min, max);
+    }
+
+    fn next_guess(&mut self, rng: &mut dyn RngCore) -> i128 {
+        self.0.at(rng.gen_range(0.25..=0.75))
+    }
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome) {
+        self.0.narrow(guess, outcome);
+    }
+}
+
+/// Error-tolerant search for lying oracles: counts how many answers each
+/// candidate contradicts and rules it out only past the lie budget. Guesses
+/// the weighted median, where candidates with more lies to spare weigh more.
+/// With no lies expected this is plain binary search.
+#[derive(Debug, Default)]
+pub struct LieTolerant {
+    max_lies: u32,
+    segments: Vec<Segment>,  // covers the range in order
+}
+
+/// A run of values that contradict the same number of answers.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    lo: i128,
+    hi: i128,
+    errors: u32,  // u32::MAX once guessed: a miss is never a lie
+}
+
+impl LieTolerant {
+    /// Combined weight of a segment's values; ruled-out values weigh nothing.
+    fn weight(&self, segment: &Segment) -> u128 {
+        match segment.errors {
+            errors if errors > self.max_lies => 0,
+            errors => u128::from(self.max_lies - errors + 1) * (segment.hi - segment.lo + 1) as u128,
+        }
+    }
+
+    /// Makes `at` the start of a segment.
+    fn split(&mut self, at: i128) {
+        let Some(i) = self.segments.iter().position(|s| s.lo < at && at <= s.hi) else { return };
+        let tail = Segment { lo: at, ..self.segments[i] };
+        self.segments[i].hi = at - 1;
+        self.segments.insert(i + 1, tail);
+    }
+
+    fn add_errors(&mut self, lo: i128, hi: i128, errors: u32) {
+        self.split(lo);
+        self.split(hi + 1);
+        for segment in self.segments.iter_mut().filter(|s| lo <= s.lo && s.hi <= hi) {
+            segment.errors = segment.errors.saturating_add(errors);
+        }
+    }
+}
+
+impl Strategy for LieTolerant {
+    fn name(&self) -> &'static str {
+        "tolerant"
+    }
+
+    fn expect_lies(&mut self, max_lies: u32) {
+        self.max_lies = max_lies;
+    }
+
+    fn start(&mut self, min: i128, max: i128) {
+        self.segments = vec![Segment { lo: min, hi: max, errors: 0 }];
+    }
+
+    fn next_guess(&mut self, _rng: &mut dyn RngCore) -> i128 {
+        let total: u128 = self.segments.iter().map(|s| self.weight(s)).sum();
+        let mut before = 0;
+        for segment in &self.segments {
+            let weight = self.weight(segment);
+            if before + weight > total / 2 {
+                let per_value = u128::from(self.max_lies - segment.errors + 1);
+                return segment.lo + ((total / 2 - before) / per_value) as i128;
+            }
+            before += weight;
+        }
+        self.segments[0].lo  // more lies than promised; nothing is consistent
+    }
+
+    fn feedback(&mut self, guess: i128, outcome: Outcome) {
+        let (min, max) = (self.segments[0].lo, self.segments[self.segments.len() - 1].hi);
+        match outcome {
+            Outcome::Low => self.add_errors(min, guess - 1, 1),
+            Outcome::High => self.add_errors(guess + 1, max, 1),
+            Outcome::Win => return,
+        }
+        self.add_errors(guess, guess, u32::MAX);
+    }
+}
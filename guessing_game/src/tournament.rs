@@ -0,0 +1,469 @@
+//! Tournaments: a roster of humans and bots plays round-robin or single
+//! elimination. Both sides of a match hunt the same secret, drawn from a
+//! seed fixed by the tournament seed and the match number.
+
+use std::cmp::{Ordering, Reverse};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+use crate::bot;
+use crate::replay::Setup;
+use crate::strategy;
+use crate::{Game, GuessRange, Number, Status};
+
+pub const MIN_ENTRANTS: usize = 2;
+pub const MAX_ENTRANTS: usize = 64;
+
+/// A knockout draw is replayed on a new secret this many times before the
+/// higher seed goes through.
+pub const MAX_REPLAYS: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Everyone plays everyone once; most points wins.
+    #[default]
+    RoundRobin,
+    /// Single elimination in a seeded bracket; the roster order is the seeding.
+    Knockout,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::RoundRobin => "Round-robin",
+            Format::Knockout => "Knockout",
+        })
+    }
+}
+
+/// Who makes the guesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Player {
+    Human,
+    /// One of `strategy::NAMES`.
+    Bot(&'static str),
+    /// A shell command speaking the `bot` protocol, started for each game.
+    Program(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entrant {
+    pub name: String,
+    pub player: Player,
+}
+
+impl FromStr for Entrant {
+    type Err = String;
+
+    /// `Ann` is a human; `bot:binary` is the binary search strategy;
+    /// `cmd:./solver --fast` is an external program.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(command) = s.strip_prefix("cmd:") {
+            let command = command.trim();
+            if command.is_empty() {
+                return Err("`cmd:` needs a command".to_string());
+            }
+            return Ok(Entrant { name: command.to_string(), player: Player::Program(command.to_string()) });
+        }
+        if let Some(name) = s.strip_prefix("bot:") {
+            let strategy = strategy::NAMES
+                .into_iter()
+                .find(|&known| known == name)
+                .ok_or_else(|| format!("unknown bot `{name}`, expected one of {}", strategy::NAMES.join(", ")))?;
+            return Ok(Entrant { name: strategy.to_string(), player: Player::Bot(strategy) });
+        }
+        if s.is_empty() {
+            return Err("player names can't be empty".to_string());
+        }
+        Ok(Entrant { name: s.to_string(), player: Player::Human })
+    }
+}
+
+impl Display for Entrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.player {
+            Player::Human | Player::Program(_) => f.write_str(&self.name),
+            Player::Bot(_) => write!(f, "{} (bot)", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RosterError {
+    Size(usize),
+    DuplicateName(String),
+}
+
+impl Display for RosterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RosterError::Size(n) => write!(f, "a tournament needs {MIN_ENTRANTS}-{MAX_ENTRANTS} players, got {n}"),
+            RosterError::DuplicateName(name) => write!(f, "{name} is entered twice"),
+        }
+    }
+}
+
+impl Error for RosterError {}
+
+/// A match to be played. Entrants are indices into the roster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pairing {
+    pub number: u32,
+    pub round: u32,
+    pub home: usize,
+    pub away: usize,
+    /// Secret (and bot moves) for both sides.
+    pub seed: u64,
+}
+
+/// How one side played a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Play {
+    pub won: bool,
+    pub attempts: u32,  // including hint penalties
+    /// `None` for built-in bots, which play without a clock.
+    pub time: Option<Duration>,
+}
+
+impl Play {
+    pub fn of<N: Number>(game: &Game<N>) -> Self {
+        Play { won: game.status() == Status::Won, attempts: game.total_attempts(), time: game.is_timed().then(|| game.elapsed()) }
+    }
+
+    /// Finding the number beats not finding it; between finders, fewer
+    /// attempts and then, if both were timed, less time (to the millisecond)
+    /// win.
+    pub fn compare(&self, other: &Play) -> Ordering {
+        match (self.won, other.won) {
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => Ordering::Equal,
+            (true, true) => other.attempts.cmp(&self.attempts).then(match (self.time, other.time) {
+                (Some(mine), Some(theirs)) => theirs.as_millis().cmp(&mine.as_millis()),
+                _ => Ordering::Equal,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub pairing: Pairing,
+    pub home: Play,
+    pub away: Play,
+    /// `None` for a draw.
+    pub winner: Option<usize>,
+}
+
+/// One line of the standings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub entrant: usize,
+    pub name: String,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    /// Two for a win, one for a draw.
+    pub points: u32,
+    /// Knockout: the last round reached.
+    pub round: u32,
+    pub attempts: u32,
+    pub time: Duration,
+}
+
+impl Standing {
+    pub const HEADER: &str = "player            played   W   D   L  points  attempts     time";
+}
+
+impl Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<16} {:>7} {:>3} {:>3} {:>3} {:>7} {:>9} {:>7.1}s",
+               self.name, self.played, self.wins, self.draws, self.losses,
+               self.points, self.attempts, self.time.as_secs_f64())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Schedule {
+    /// Every pairing up front: (round, home, away).
+    RoundRobin(Vec<(u32, usize, usize)>),
+    Knockout {
+        round: u32,
+        slots: Vec<Option<usize>>,  // this round's bracket; `None` is a bye
+        through: Vec<Option<usize>>,  // winners of this round so far
+        replays: u32,  // of the current pairing
+    },
+}
+
+/// The matches and results of one tournament. Frontends play each
+/// `next_match` and hand both games to `record`.
+#[derive(Debug, Clone)]
+pub struct Tournament<N = u32> {
+    entrants: Vec<Entrant>,
+    format: Format,
+    range: GuessRange<N>,
+    max_attempts: Option<u32>,
+    seed: u64,
+    bot_timeout: Duration,  // per move, for programs
+    schedule: Schedule,
+    results: Vec<MatchResult>,
+    reached: Vec<u32>,  // knockout round per entrant
+}
+
+impl<N: Number> Tournament<N> {
+    pub fn new(
+        entrants: Vec<Entrant>,
+        format: Format,
+        range: GuessRange<N>,
+        max_attempts: Option<u32>,
+        seed: u64,
+    ) -> Result<Self, RosterError> {
+        if !(MIN_ENTRANTS..=MAX_ENTRANTS).contains(&entrants.len()) {
+            return Err(RosterError::Size(entrants.len()));
+        }
+        for (i, entrant) in entrants.iter().enumerate() {
+            if entrants[..i].iter().any(|other| other.name == entrant.name) {
+                return Err(RosterError::DuplicateName(entrant.name.clone()));
+            }
+        }
+        let schedule = match format {
+            Format::RoundRobin => Schedule::RoundRobin(round_robin(entrants.len())),
+            Format::Knockout => Schedule::Knockout {
+                round: 1,
+                slots: bracket(entrants.len()),
+                through: Vec::new(),
+                replays: 0,
+            },
+        };
+        let reached = vec![1; entrants.len()];
+        let mut tournament = Tournament {
+            entrants,
+            format,
+            range,
+            max_attempts,
+            seed,
+            bot_timeout: bot::DEFAULT_TIMEOUT,
+            schedule,
+            results: Vec::new(),
+            reached,
+        };
+        tournament.settle();
+        Ok(tournament)
+    }
+
+    /// How long a program may take over each move.
+    pub fn with_bot_timeout(mut self, timeout: Duration) -> Self {
+        self.bot_timeout = timeout;
+        self
+    }
+
+    pub fn bot_timeout(&self) -> Duration {
+        self.bot_timeout
+    }
+
+    pub fn entrants(&self) -> &[Entrant] {
+        &self.entrants
+    }
+
+    pub fn entrant(&self, index: usize) -> &Entrant {
+        &self.entrants[index]
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn range(&self) -> GuessRange<N> {
+        self.range
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn results(&self) -> &[MatchResult] {
+        &self.results
+    }
+
+    /// `None` once every match has been played.
+    pub fn next_match(&self) -> Option<Pairing> {
+        let number = self.results.len() as u32 + 1;
+        let seed = self.seed.wrapping_add(u64::from(number));
+        let (round, home, away) = match &self.schedule {
+            Schedule::RoundRobin(pairings) => *pairings.get(self.results.len())?,
+            Schedule::Knockout { round, slots, through, .. } => {
+                let at = 2 * through.len();
+                (*round, slots.get(at).copied()??, slots.get(at + 1).copied()??)
+            }
+        };
+        Some(Pairing { number, round, home, away, seed })
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.next_match().is_none()
+    }
+
+    /// A fresh game for one side of `pairing`; both sides get the same secret.
+    pub fn game(&self, pairing: &Pairing) -> Game<N> {
+        Setup::new(self.range, pairing.seed)
+            .with_max_attempts(self.max_attempts)
+            .game()
+            .expect("the range came from N")
+    }
+
+    /// Records `pairing` as played and moves the schedule on.
+    pub fn record(&mut self, pairing: &Pairing, home: &Game<N>, away: &Game<N>) -> &MatchResult {
+        let (home_play, away_play) = (Play::of(home), Play::of(away));
+        let winner = match home_play.compare(&away_play) {
+            Ordering::Greater => Some(pairing.home),
+            Ordering::Less => Some(pairing.away),
+            Ordering::Equal => None,
+        };
+        if let Schedule::Knockout { round, through, replays, .. } = &mut self.schedule {
+            let advancing = match winner {
+                Some(winner) => Some(winner),
+                None if *replays >= MAX_REPLAYS => Some(pairing.home.min(pairing.away)),  // the higher seed
+                None => None,
+            };
+            match advancing {
+                Some(advancing) => {
+                    through.push(Some(advancing));
+                    self.reached[advancing] = *round + 1;
+                    *replays = 0;
+                }
+                None => *replays += 1,
+            }
+        }
+        self.results.push(MatchResult { pairing: *pairing, home: home_play, away: away_play, winner });
+        self.settle();
+        self.results.last().expect("just pushed")
+    }
+
+    /// Passes byes through and starts the next knockout round when this one
+    /// is complete.
+    fn settle(&mut self) {
+        let Schedule::Knockout { round, slots, through, .. } = &mut self.schedule else { return };
+        loop {
+            let at = 2 * through.len();
+            if slots.len() <= 1 {
+                return;
+            }
+            if at >= slots.len() {
+                *slots = std::mem::take(through);
+                *round += 1;
+                continue;
+            }
+            match (slots[at], slots[at + 1]) {
+                (Some(_), Some(_)) => return,
+                (bye, None) | (None, bye) => {
+                    if let Some(entrant) = bye {
+                        self.reached[entrant] = *round + 1;
+                    }
+                    through.push(bye);
+                }
+            }
+        }
+    }
+
+    /// The knockout winner, or the top of the round-robin table once over;
+    /// `None` while the top is shared.
+    pub fn champion(&self) -> Option<usize> {
+        match self.leaders().as_slice() {
+            &[champion] if self.is_over() => Some(champion),
+            _ => None,
+        }
+    }
+
+    /// Everyone level with the top of the standings.
+    pub fn leaders(&self) -> Vec<usize> {
+        let standings = self.standings();
+        let Some(top) = standings.first().map(|s| self.rank(s)) else { return Vec::new() };
+        standings.iter().take_while(|s| self.rank(s) == top).map(|s| s.entrant).collect()
+    }
+
+    /// Best first: knockout by round reached, then points, wins, and the
+    /// attempts and time of the games won; roster order breaks any remaining
+    /// tie. Lost games don't count towards attempts or time, so giving up
+    /// early gains nothing.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut table: Vec<Standing> = self.entrants.iter().enumerate().map(|(entrant, e)| Standing {
+            entrant,
+            name: e.name.clone(),
+            played: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            points: 0,
+            round: self.reached[entrant],
+            attempts: 0,
+            time: Duration::ZERO,
+        }).collect();
+
+        for result in &self.results {
+            for (side, play) in [(result.pairing.home, result.home), (result.pairing.away, result.away)] {
+                let standing = &mut table[side];
+                standing.played += 1;
+                if play.won {
+                    standing.attempts += play.attempts;
+                    standing.time += play.time.unwrap_or_default();
+                }
+                match result.winner {
+                    Some(winner) if winner == side => {
+                        standing.wins += 1;
+                        standing.points += 2;
+                    }
+                    Some(_) => standing.losses += 1,
+                    None => {
+                        standing.draws += 1;
+                        standing.points += 1;
+                    }
+                }
+            }
+        }
+
+        table.sort_by_key(|s| (self.rank(s), s.entrant));
+        table
+    }
+
+    /// What the standings are sorted by, best first.
+    fn rank(&self, s: &Standing) -> (Reverse<u32>, Reverse<u32>, Reverse<u32>, u32, Duration) {
+        let round = if self.format == Format::Knockout { s.round } else { 0 };
+        (Reverse(round), Reverse(s.points), Reverse(s.wins), s.attempts, s.time)
+    }
+}
+
+/// The circle method: one entrant stays put while the rest rotate, so
+/// everyone meets everyone once. An odd roster sits one out each round.
+fn round_robin(entrants: usize) -> Vec<(u32, usize, usize)> {
+    let mut circle: Vec<Option<usize>> = (0..entrants).map(Some).collect();
+    if entrants % 2 == 1 {
+        circle.push(None);
+    }
+    let n = circle.len();
+    let mut pairings = Vec::new();
+    for round in 1..n as u32 {
+        for i in 0..n / 2 {
+            if let (Some(a), Some(b)) = (circle[i], circle[n - 1 - i]) {
+                // Alternate sides so nobody always plays first.
+                pairings.push(if round % 2 == 0 { (round, b, a) } else { (round, a, b) });
+            }
+        }
+        circle[1..].rotate_right(1);
+    }
+    pairings
+}
+
+/// First-round slots for a seeded bracket: seed 1 meets the lowest seed, and
+/// the top two seeds can only meet in the final. Missing entrants are byes.
+fn bracket(entrants: usize) -> Vec<Option<usize>> {
+    let size = entrants.next_power_of_two();
+    let mut order = vec![0];
+    while order.len() < size {
+        let mirror = 2 * order.len() - 1;
+        order = order.iter().flat_map(|&seed| [seed, mirror - seed]).collect();
+    }
+    order.into_iter().map(|seed| (seed < entrants).then_some(seed)).collect()
+}
@@ -0,0 +1,227 @@
+//! Full-screen terminal frontend (ratatui + crossterm).
+
+use std::io;
+use std::time::Duration;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use crate::events::{self, GameEvent, Observer};
+use crate::hint;
+use crate::save::SaveSlot;
+use crate::{Game, Number, Outcome, Status};
+
+const TICK: Duration = Duration::from_millis(200);  // redraw rate for the timer
+
+/// Plays one game full-screen. Emits the same events as `Session::play`.
+#[derive(Default)]
+pub struct Tui {
+    observers: Vec<Observer>,
+    input: String,
+    message: String,
+    save_slot: Option<SaveSlot>,
+}
+
+impl Tui {
+    pub fn new() -> Self {
+        Tui::default()
+    }
+
+    pub fn with_observer(mut self, observer: impl FnMut(&GameEvent) + Send + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Typing `save` saves the game, and Ctrl-C saves and quits instead of
+    /// giving up. The file is deleted once the game is over.
+    pub fn with_save_slot(mut self, slot: SaveSlot) -> Self {
+        self.save_slot = Some(slot);
+        self
+    }
+
+    /// Takes over the terminal until the player leaves; always restores it.
+    pub fn play<N: Number>(&mut self, game: &mut Game<N>) -> io::Result<()> {
+        let mut terminal = ratatui::try_init()?;
+        let result = self.run(&mut terminal, game);
+        ratatui::restore();
+        result
+    }
+
+    fn run<N: Number>(&mut self, terminal: &mut DefaultTerminal, game: &mut Game<N>) -> io::Result<()> {
+        events::notify(&mut self.observers, GameEvent::created(game));
+        game.start_clock();
+        let mut finished = false;
+
+        loop {
+            terminal.draw(|frame| render(frame, game, &self.input, &self.message, game.elapsed()))?;
+
+            if event::poll(TICK)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                if game.is_over() {
+                    match key.code {
+                        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+                        _ => continue,
+                    }
+                }
+
+                match key.code {
+                    KeyCode::Esc => game.give_up(),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => match &self.save_slot {
+                        Some(slot) if game.total_attempts() > 0 => return slot.write(game),
+                        _ => game.give_up(),
+                    },
+                    KeyCode::Char(c) if c.is_ascii_alphanumeric() || c == '-' || c == ' ' => self.input.push(c),
+                    KeyCode::Backspace => {
+                        self.input.pop();
+                    }
+                    KeyCode::Enter => self.submit(game),
+                    _ => {}
+                }
+            }
+            game.check_time();  // the countdown can end the game between keys
+
+            if game.is_over() && !finished {
+                finished = true;
+                if let Some(event) = GameEvent::finished(game) {
+                    events::notify(&mut self.observers, event);
+                }
+                if let Some(slot) = &self.save_slot {
+                    slot.discard()?;
+                }
+                self.message = match game.status() {
+                    Status::Won => format!("You win! Score: {}. Enter exits.", game.score()),
+                    Status::Lost => format!("Out of attempts, the number was {}. Enter exits.", game.secret()),
+                    Status::OutOfTime => format!("Time's up, the number was {}. Enter exits.", game.secret()),
+                    _ => format!("You gave up, the number was {}. Enter exits.", game.secret()),
+                };
+            }
+        }
+    }
+
+    fn submit<N: Number>(&mut self, game: &mut Game<N>) {
+        let input = std::mem::take(&mut self.input);
+        if let Some(slot) = self.save_slot.as_ref().filter(|_| input.trim().eq_ignore_ascii_case("save")) {
+            self.message = match slot.write(game) {
+                Ok(()) => format!("Game saved to {}.", slot.path.display()),
+                Err(err) => format!("Could not save: {err}"),
+            };
+            return;
+        }
+        if let Some(request) = hint::parse_request(&input) {
+            self.message = match request.map(|hint| (hint, game.hint(hint))) {
+                Ok((hint, Ok((clue, new)))) => {
+                    if new {
+                        events::notify(&mut self.observers, GameEvent::hint(game, hint, &clue));
+                    }
+                    format!("Hint: {clue}")
+                }
+                Ok((_, Err(err))) => format!("No hint: {err}"),
+                Err(usage) => usage,
+            };
+            return;
+        }
+        match game.range().parse(&input) {
+            Ok(guess) => {
+                self.message = match game.guess(guess) {
+                    Outcome::Low => format!("{guess} is too low!"),
+                    Outcome::High => format!("{guess} is too high!"),
+                    Outcome::Win => format!("{guess} is right!"),
+                };
+                for event in GameEvent::last_guess(game).into_iter().flatten() {
+                    events::notify(&mut self.observers, event);
+                }
+            }
+            Err(err) => self.message = format!("{err}, try again"),
+        }
+    }
+}
+
+/// Draws one frame: the shrinking interval, guess history, counters,
+/// the input line and key help.
+pub fn render<N: Number>(frame: &mut Frame, game: &Game<N>, input: &str, message: &str, elapsed: Duration) {
+    let [bar, middle, entry, help] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(4),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [history, status] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(middle);
+
+    let candidates = game.candidates();
+    let title = format!(" Guess the number ({}) ", game.range());
+    let bar_block = Block::bordered().title(title).title_bottom(format!(" still possible: {candidates} "));
+    let line = interval_line(game, bar_block.inner(bar));
+    frame.render_widget(Paragraph::new(line).block(bar_block), bar);
+
+    let items: Vec<ListItem> = game.history().iter().enumerate().rev()
+        .map(|(i, &(guess, outcome))| {
+            let (marker, color) = match outcome {
+                Outcome::Low => ("▲ too low", Color::Yellow),
+                Outcome::High => ("▼ too high", Color::Magenta),
+                Outcome::Win => ("✔ correct", Color::Green),
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:>3}. {guess:<12}", i + 1)),
+                Span::styled(marker, Style::new().fg(color)),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(Block::bordered().title(" Guesses ")), history);
+
+    let mut attempts = match game.max_attempts() {
+        Some(max) => format!("Attempts: {}/{max}", game.attempts()),
+        None => format!("Attempts: {}", game.attempts()),
+    };
+    if game.penalty() > 0 {
+        attempts += &format!(" +{}", game.penalty());  // hint penalty
+    }
+    let clock = match game.time_limit() {
+        Some(limit) => format!("Time left: {}", mm_ss(limit.saturating_sub(elapsed))),
+        None => format!("Time: {}", mm_ss(elapsed)),
+    };
+    let lines = vec![
+        Line::from(attempts),
+        Line::from(clock),
+        Line::from(format!("Score: {}", game.score())),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Status ")), status);
+
+    let cursor = if game.is_over() { "" } else { "_" };
+    let lines = vec![Line::from(format!("> {input}{cursor}")).bold(), Line::from(message.to_string())];
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Your guess ")), entry);
+
+    let keys = if game.is_over() {
+        " Enter/Esc: exit"
+    } else {
+        " 0-9: type  Enter: guess  hint: help  Esc: give up"
+    };
+    frame.render_widget(Paragraph::new(keys).dim(), help);
+}
+
+fn mm_ss(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// The full range as a row of cells, with the candidates still open lit up.
+fn interval_line<N: Number>(game: &Game<N>, area: Rect) -> Line<'static> {
+    let width = area.width as usize;
+    let (range, candidates) = (game.range(), game.candidates());
+    let size = range.size() as f64;
+    let offset = |n: N| (n.to_i128() - range.min().to_i128()) as f64;
+    let start = ((offset(candidates.min()) / size) * width as f64).floor() as usize;
+    let end = ((offset(candidates.max()) + 1.0) / size * width as f64).ceil() as usize;
+    let (start, end) = (start.min(width.saturating_sub(1)), end.clamp(start + 1, width.max(1)));
+
+    Line::from(vec![
+        Span::styled("░".repeat(start), Style::new().dark_gray()),
+        Span::styled("█".repeat(end - start), Style::new().green()),
+        Span::styled("░".repeat(width.saturating_sub(end)), Style::new().dark_gray()),
+    ])
+}
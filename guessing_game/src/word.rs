@@ -0,0 +1,298 @@
+//! Word mode, Wordle style: guess a secret word and learn, letter by letter,
+//! whether each is in the right place, elsewhere in the word, or absent.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use rand::Rng;
+use crate::Status;
+
+/// The word list that ships with the game: common five-letter words.
+pub const BUILTIN: &str = include_str!("../words/english.txt");
+
+/// Six guesses, as in the original.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 6;
+
+/// Words of one length that can be secrets and guesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    length: usize,
+    words: Vec<String>,  // sorted, for drawing secrets reproducibly
+    known: HashSet<String>,
+}
+
+impl Dictionary {
+    /// One word per line; blank lines and `#` comments are skipped, as are
+    /// words of other lengths or with anything but ASCII letters. Fails if no
+    /// word is left.
+    pub fn parse(list: &str, length: usize) -> Result<Self, String> {
+        let mut words: Vec<String> = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .filter(|word| word.len() == length && word.bytes().all(|b| b.is_ascii_alphabetic()))
+            .map(str::to_ascii_lowercase)
+            .collect();
+        words.sort();
+        words.dedup();
+        if words.is_empty() {
+            return Err(format!("the word list has no {length}-letter words"));
+        }
+        let known = words.iter().cloned().collect();
+        Ok(Dictionary { length, words, known })
+    }
+
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN, 5).expect("the builtin list has five-letter words")
+    }
+
+    /// Loads a custom word list file.
+    pub fn load(path: &Path, length: usize) -> io::Result<Self> {
+        let list = fs::read_to_string(path)?;
+        Self::parse(&list, length).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.known.contains(word)
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.words[rng.gen_range(0..self.words.len())].clone()
+    }
+}
+
+/// What a guess revealed about one letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Correct,  // right letter, right place
+    Present,  // in the word, elsewhere
+    Absent,
+}
+
+impl Mark {
+    /// `+`, `?` or `-`, for line-based output.
+    pub fn symbol(self) -> char {
+        match self {
+            Mark::Correct => '+',
+            Mark::Present => '?',
+            Mark::Absent => '-',
+        }
+    }
+}
+
+/// Marks `guess` against `secret`. A letter guessed more often than it occurs
+/// is only marked present as many times as it occurs, greens first.
+pub fn mark(secret: &str, guess: &str) -> Vec<Mark> {
+    let (secret, guess) = (secret.as_bytes(), guess.as_bytes());
+    let mut marks = vec![Mark::Absent; guess.len()];
+    let mut unmatched = [0u8; 26];
+    for (i, (&s, &g)) in secret.iter().zip(guess).enumerate() {
+        if s == g {
+            marks[i] = Mark::Correct;
+        } else {
+            unmatched[usize::from(s - b'a')] += 1;
+        }
+    }
+    for (i, &g) in guess.iter().enumerate() {
+        let left = &mut unmatched[usize::from(g - b'a')];
+        if marks[i] == Mark::Absent && *left > 0 {
+            *left -= 1;
+            marks[i] = Mark::Present;
+        }
+    }
+    marks
+}
+
+/// `+?---`
+pub fn pattern(marks: &[Mark]) -> String {
+    marks.iter().map(|mark| mark.symbol()).collect()
+}
+
+/// Why a guess was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    Empty,
+    WrongLength { expected: usize, found: usize },
+    NotLetters(String),
+    NotAWord(String),
+    /// Hard mode: a revealed letter was dropped.
+    MustKeep { letter: char, position: Option<usize> },
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordError::Empty => write!(f, "no guess entered"),
+            WordError::WrongLength { expected, found } => write!(f, "expected {expected} letters, got {found}"),
+            WordError::NotLetters(word) => write!(f, "`{word}` is not a word"),
+            WordError::NotAWord(word) => write!(f, "`{word}` is not in the word list"),
+            WordError::MustKeep { letter, position: Some(position) } => {
+                write!(f, "hard mode: letter {} must be {}", position + 1, letter.to_ascii_uppercase())
+            }
+            WordError::MustKeep { letter, position: None } => {
+                write!(f, "hard mode: the guess must contain {}", letter.to_ascii_uppercase())
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
+
+/// One round of word mode; the word counterpart of `Game`.
+#[derive(Debug, Clone)]
+pub struct WordGame {
+    dictionary: Dictionary,
+    secret: String,
+    hard: bool,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    status: Status,
+    history: Vec<(String, Vec<Mark>)>,
+}
+
+impl WordGame {
+    /// Draws the secret from `dictionary`; six guesses by default.
+    pub fn new<R: Rng + ?Sized>(dictionary: Dictionary, rng: &mut R) -> Self {
+        let secret = dictionary.sample(rng);
+        Self::with_secret(dictionary, &secret)
+    }
+
+    /// Panics if `secret` is not in the dictionary.
+    pub fn with_secret(dictionary: Dictionary, secret: &str) -> Self {
+        assert!(dictionary.contains(secret), "secret {secret} is not in the dictionary");
+        WordGame {
+            secret: secret.to_string(),
+            dictionary,
+            hard: false,
+            attempts: 0,
+            max_attempts: Some(DEFAULT_MAX_ATTEMPTS),
+            status: Status::Playing,
+            history: Vec::new(),
+        }
+    }
+
+    /// `None` allows unlimited guesses.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Hard mode: revealed hints must be used in every later guess.
+    pub fn with_hard_mode(mut self, hard: bool) -> Self {
+        self.hard = hard;
+        self
+    }
+
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dictionary
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.hard
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts.map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn history(&self) -> &[(String, Vec<Mark>)] {
+        &self.history
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    /// Validates a typed guess: length, letters, the word list and, in hard
+    /// mode, the hints revealed so far. Returns the word in lowercase.
+    pub fn parse(&self, input: &str) -> Result<String, WordError> {
+        let word = input.trim().to_ascii_lowercase();
+        if word.is_empty() {
+            return Err(WordError::Empty);
+        }
+        if !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(WordError::NotLetters(word));
+        }
+        if word.len() != self.dictionary.length {
+            return Err(WordError::WrongLength { expected: self.dictionary.length, found: word.len() });
+        }
+        if !self.dictionary.contains(&word) {
+            return Err(WordError::NotAWord(word));
+        }
+        if self.hard {
+            self.check_hard(&word)?;
+        }
+        Ok(word)
+    }
+
+    /// Greens stay in place; greens and yellows are used at least as often
+    /// as they were revealed.
+    fn check_hard(&self, word: &str) -> Result<(), WordError> {
+        let word = word.as_bytes();
+        for (guess, marks) in &self.history {
+            let guess = guess.as_bytes();
+            for (i, mark) in marks.iter().enumerate() {
+                if *mark == Mark::Correct && word[i] != guess[i] {
+                    return Err(WordError::MustKeep { letter: char::from(guess[i]), position: Some(i) });
+                }
+            }
+            for (&letter, _) in guess.iter().zip(marks).filter(|&(_, &mark)| mark != Mark::Absent) {
+                let revealed = guess.iter().zip(marks).filter(|&(&l, &m)| l == letter && m != Mark::Absent).count();
+                if word.iter().filter(|&&l| l == letter).count() < revealed {
+                    return Err(WordError::MustKeep { letter: char::from(letter), position: None });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks as-is; frontends validate input with `parse` first. Guesses
+    /// made after the game is over are answered but not counted.
+    pub fn guess(&mut self, word: &str) -> Vec<Mark> {
+        let marks = mark(&self.secret, word);
+        if self.is_over() {
+            return marks;
+        }
+
+        self.attempts += 1;
+        self.history.push((word.to_string(), marks.clone()));
+        if word == self.secret {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        marks
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_over() {
+            self.status = Status::GaveUp;
+        }
+    }
+}
@@ -0,0 +1,84 @@
+#![cfg(unix)]
+
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use guessing_game::bot::{BotError, ExternalBot, Message};
+use guessing_game::events::GameEvent;
+use guessing_game::{Game, Outcome, Session, Status};
+
+/// Guesses 1, 2, 3, ... until it gets the result.
+const COUNTER: &str = r#"n=0; while read -r line; do case "$line" in *'"result"'*) exit 0;; esac; n=$((n+1)); echo "{\"guess\": $n}"; done"#;
+
+fn play(command: &str, game: &mut Game) -> (String, Option<BotError>) {
+    let mut bot = ExternalBot::spawn(command).unwrap().with_timeout(Duration::from_millis(500));
+    let mut session = Session::new(Cursor::new(""), Vec::new());
+    let forfeit = session.play_bot(game, &mut bot).unwrap();
+    (String::from_utf8(session.into_inner().1).unwrap(), forfeit)
+}
+
+#[test]
+fn messages_are_tagged_json() {
+    let start = Message::Start { min: 1, max: 100, max_attempts: Some(7), max_lies: 0 };
+    assert_eq!(
+        serde_json::to_string(&start).unwrap(),
+        r#"{"type":"start","min":1,"max":100,"max_attempts":7,"max_lies":0}"#
+    );
+    let feedback = Message::Feedback { guess: 50, outcome: Outcome::High };
+    assert_eq!(serde_json::to_string(&feedback).unwrap(), r#"{"type":"feedback","guess":50,"outcome":"high"}"#);
+}
+
+#[test]
+fn a_bot_plays_a_game() {
+    let mut game = Game::with_secret(3);
+    let (output, forfeit) = play(COUNTER, &mut game);
+    assert!(forfeit.is_none());
+    assert_eq!(game.status(), Status::Won);
+    assert!(output.ends_with("1: too low\n2: too low\n3: right\nFound in 3 attempts, score: 664\n"), "{output}");
+}
+
+#[test]
+fn bots_see_the_same_events_as_players() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let mut bot = ExternalBot::spawn(COUNTER).unwrap();
+    let mut session =
+        Session::new(Cursor::new(""), Vec::new()).with_observer(move |event| sink.lock().unwrap().push(event.clone()));
+    session.play_bot(&mut Game::with_secret(2), &mut bot).unwrap();
+    let events = events.lock().unwrap();
+    assert!(matches!(events.first(), Some(GameEvent::Created { .. })));
+    assert!(matches!(events.last(), Some(GameEvent::Won { attempts: 2, .. })));
+}
+
+#[test]
+fn slow_bots_time_out() {
+    let mut game = Game::with_secret(3);
+    let (output, forfeit) = play("sleep 5", &mut game);
+    assert!(matches!(forfeit, Some(BotError::Timeout(_))));
+    assert_eq!(game.status(), Status::GaveUp);
+    assert!(output.contains("The bot forfeits: no move within 0.5s.\n"), "{output}");
+}
+
+#[test]
+fn broken_bots_forfeit() {
+    let (_, forfeit) = play("echo hello", &mut Game::with_secret(3));
+    assert!(matches!(forfeit, Some(BotError::Protocol(_))), "{forfeit:?}");
+
+    let (_, forfeit) = play(r#"echo '{"guess": 101}'"#, &mut Game::with_secret(3));
+    assert!(matches!(forfeit, Some(BotError::Protocol(ref err)) if err == "101 is outside 1-100"), "{forfeit:?}");
+
+    let (_, forfeit) = play("read -r line; exit 3", &mut Game::with_secret(3));
+    match forfeit {
+        Some(BotError::Exited(Some(status))) => assert_eq!(status.code(), Some(3)),
+        other => panic!("expected an exit, got {other:?}"),
+    }
+}
+
+#[test]
+fn bots_know_about_limits() {
+    let mut game = Game::with_secret(50).with_max_attempts(Some(2));
+    let (output, forfeit) = play(COUNTER, &mut game);
+    assert!(forfeit.is_none());
+    assert_eq!(game.status(), Status::Lost);
+    assert!(output.ends_with("Out of attempts, the number was 50.\n"), "{output}");
+}
@@ -0,0 +1,56 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+        .args(args)
+        .env("GUESSING_GAME_DATA_DIR", std::env::temp_dir().join("guessing_game_cli_tests"))
+        .env_remove("GUESSING_GAME_SEED")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn bare_options_play_a_seeded_game() {
+    let bare = run(&["--seed", "3", "-d", "easy"], "");
+    let explicit = run(&["play", "--seed", "3", "--difficulty", "easy"], "");
+    assert!(bare.status.success());
+    assert!(stdout(&bare).starts_with("Guess the number (1-10)!"));
+    assert_eq!(stdout(&bare), stdout(&explicit));
+}
+
+#[test]
+fn rejects_invalid_options() {
+    assert_eq!(run(&["--max-attempts", "0"], "").status.code(), Some(2));
+    assert_eq!(run(&["--difficulty", "nightmare"], "").status.code(), Some(2));
+    assert_eq!(run(&["--min", "10", "--max", "1"], "").status.code(), Some(2));
+}
+
+#[test]
+fn negative_ranges_and_completions() {
+    let output = run(&["--min", "-5", "--max", "5"], "");
+    assert!(stdout(&output).starts_with("Guess the number (-5 to 5)!"));
+
+    let output = run(&["completions", "bash"], "");
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("simulate"));
+}
+
+#[test]
+fn feedback_model_is_selectable() {
+    let output = run(&["--seed", "3", "--feedback", "distance"], "1\n");
+    let out = stdout(&output);
+    assert!(["Burning!", "Hot!", "Warm.", "Cold.", "Freezing!"].iter().any(|line| out.contains(line)), "{out}");
+    assert_eq!(run(&["--feedback", "psychic"], "").status.code(), Some(2));
+    assert_eq!(run(&["--lies", "2", "--feedback", "distance"], "").status.code(), Some(2));
+    assert!(run(&["--seed", "3", "--lies", "2", "--feedback", "low-high"], "").status.success());
+}
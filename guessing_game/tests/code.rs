@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant};
+use guessing_game::code::{CodeError, CodeGame, Rules, Solver};
+use guessing_game::{rng, Status};
+
+#[test]
+fn parses_guesses_by_the_rules() {
+    let distinct = Rules::default();
+    assert_eq!(distinct.parse(" 01 23\n").unwrap().to_string(), "0123");
+    assert_eq!(distinct.parse(""), Err(CodeError::Empty));
+    assert_eq!(distinct.parse("12a4"), Err(CodeError::NotADigit('a')));
+    assert_eq!(distinct.parse("1214"), Err(CodeError::Repeated(1)));
+    assert_eq!(distinct.parse("123"), Err(CodeError::WrongLength { expected: 4, found: 3 }));
+    assert_eq!(Rules::new(4, true).unwrap().parse("1214").unwrap().digits(), [1, 2, 1, 4]);
+    assert!(Rules::new(0, false).is_err() && Rules::new(11, true).is_err());
+}
+
+#[test]
+fn scores_bulls_and_cows() {
+    let rules = Rules::new(4, true).unwrap();
+    let code = |s: &str| rules.parse(s).unwrap();
+    let score = |secret: &str, guess: &str| {
+        let score = code(secret).score(&code(guess));
+        (score.bulls, score.cows)
+    };
+    assert_eq!(score("1234", "1234"), (4, 0));
+    assert_eq!(score("1234", "4321"), (0, 4));
+    assert_eq!(score("1122", "1212"), (2, 2));
+    assert_eq!(score("1122", "1111"), (2, 0), "extra copies are neither bulls nor cows");
+    assert_eq!(code("1234").score(&code("1350")).to_string(), "1 bull, 1 cow");
+}
+
+#[test]
+fn games_end_on_a_crack_or_the_attempt_limit() {
+    let rules = Rules::default();
+    let mut game = CodeGame::with_secret(rules, rules.parse("5271").unwrap()).with_max_attempts(Some(2));
+    game.guess(&rules.parse("1234").unwrap());
+    assert_eq!(game.attempts_left(), Some(1));
+    game.guess(&rules.parse("5271").unwrap());
+    assert_eq!(game.status(), Status::Won);
+
+    let mut game = CodeGame::new(rules, &mut rng::seeded(1)).with_max_attempts(Some(1));
+    let wrong = if game.secret().digits()[0] == 9 { "0123" } else { "9876" };
+    game.guess(&rules.parse(wrong).unwrap());
+    assert!(game.is_over());
+    assert_eq!(rules.all_codes().len(), 5040);
+    assert_eq!(Rules::new(4, true).unwrap().all_codes().len(), 10_000);
+}
+
+#[test]
+fn knuth_solver_cracks_codes_quickly() {
+    let rules = Rules::new(3, false).unwrap();
+    let mut secrets = rng::seeded(4);
+    for _ in 0..10 {
+        let mut game = CodeGame::new(rules, &mut secrets);
+        let mut solver = Solver::new(rules).unwrap();
+        while !game.is_over() {
+            let guess = solver.next_guess().unwrap();
+            let score = game.guess(&guess);
+            solver.feedback(&guess, score);
+            assert!(solver.candidates().contains(game.secret()));
+        }
+        assert!(game.attempts() <= 7, "{} took {}", game.secret(), game.attempts());
+    }
+}
+
+#[test]
+fn solver_keeps_long_codes_quick() {
+    let rules = Rules::new(Solver::MAX_LENGTH_WITH_REPEATS, true).unwrap();
+    let started = Instant::now();
+    let mut game = CodeGame::new(rules, &mut rng::seeded(1));
+    let mut solver = Solver::new(rules).unwrap();
+    while !game.is_over() {
+        let guess = solver.next_guess().unwrap();
+        let score = game.guess(&guess);
+        solver.feedback(&guess, score);
+    }
+    assert_eq!(game.status(), Status::Won);
+    assert!(started.elapsed() < Duration::from_secs(10), "took {:?}", started.elapsed());
+}
+
+#[test]
+fn solver_refuses_codes_too_long_to_enumerate() {
+    assert!(Solver::new(Rules::new(Solver::MAX_LENGTH, false).unwrap()).is_ok());
+    let err = Solver::new(Rules::new(Solver::MAX_LENGTH + 1, false).unwrap()).unwrap_err();
+    assert_eq!(err, "the solver cracks codes of up to 6 distinct digits or 5 with repeats, not 7 distinct digits");
+    assert!(Solver::new(Rules::new(Solver::MAX_LENGTH_WITH_REPEATS + 1, true).unwrap()).is_err());
+}
@@ -0,0 +1,41 @@
+use guessing_game::feedback::{self, Distance, Feedback, LowHigh, WarmerColder};
+use guessing_game::Outcome;
+
+#[test]
+fn warmer_colder_compares_with_the_previous_guess() {
+    let mut feedback = WarmerColder::default();
+    feedback.start(1, 100);
+    assert!(feedback.miss(10, 42, Outcome::Low).starts_with("Missed!"));
+    assert_eq!(feedback.miss(30, 42, Outcome::Low), "Warmer!");
+    assert_eq!(feedback.miss(60, 42, Outcome::High), "Colder!");
+    assert_eq!(feedback.miss(24, 42, Outcome::Low), "Same distance!");
+
+    feedback.start(1, 100);
+    assert!(feedback.miss(41, 42, Outcome::Low).starts_with("Missed!"), "a new game forgets the last guess");
+}
+
+#[test]
+fn distance_buckets_scale_with_the_range() {
+    let mut feedback = Distance::default();
+    feedback.start(1, 100);
+    let lines: Vec<String> = [41, 37, 32, 20, 1].iter().map(|&guess| feedback.miss(guess, 42, Outcome::Low)).collect();
+    assert_eq!(lines, ["Burning!", "Hot!", "Warm.", "Cold.", "Freezing!"]);
+
+    feedback.start(-5_000, 4_999);
+    assert_eq!(feedback.miss(-100, 100, Outcome::Low), "Burning!");
+}
+
+#[test]
+fn looks_models_up_by_name() {
+    for name in feedback::NAMES {
+        assert_eq!(feedback::by_name(name).unwrap().name(), name);
+    }
+    assert!(feedback::by_name("psychic").is_none());
+}
+
+#[test]
+fn low_high_repeats_the_games_answer() {
+    let mut feedback = LowHigh;
+    assert_eq!(feedback.miss(10, 42, Outcome::Low), "Too low!");
+    assert_eq!(feedback.miss(10, 42, Outcome::High), "Too high!", "a lie is passed on as told");
+}
@@ -0,0 +1,140 @@
+use std::thread;
+use std::time::Duration;
+use guessing_game::{score, Difficulty, Game, GuessRange, InvalidRange, Outcome, Status};
+
+#[test]
+fn guesses_compare_against_secret() {
+    let mut game = Game::with_secret(42);
+    assert_eq!(game.guess(10), Outcome::Low);
+    assert_eq!(game.guess(90), Outcome::High);
+    assert_eq!(game.guess(42), Outcome::Win);
+}
+
+#[test]
+fn random_secret_is_in_range() {
+    for _ in 0..100 {
+        let secret = Game::new().secret();
+        assert!((1..=100).contains(&secret));
+    }
+}
+
+#[test]
+fn same_seed_replays_same_secrets() {
+    let mut a = guessing_game::rng::seeded(7);
+    let mut b = guessing_game::rng::seeded(7);
+    for _ in 0..20 {
+        assert_eq!(Game::with_rng(&mut a).secret(), Game::with_rng(&mut b).secret());
+    }
+}
+
+#[test]
+fn secrets_follow_the_range() {
+    let mut rng = guessing_game::rng::seeded(1);
+    let range = GuessRange::new(-20i64, -10).unwrap();
+    for _ in 0..100 {
+        assert!(range.contains(Game::in_range(range, &mut rng).secret()));
+    }
+
+    let huge = GuessRange::new(u64::MAX - 1, u64::MAX).unwrap();
+    assert!(Game::in_range(huge, &mut rng).secret() >= u64::MAX - 1);
+}
+
+#[test]
+fn presets_and_validation() {
+    assert_eq!(Difficulty::Hard.range::<u64>(), GuessRange::new(1, 1_000).unwrap());
+    assert_eq!("Insane".parse::<Difficulty>(), Ok(Difficulty::Insane));
+    assert!("nightmare".parse::<Difficulty>().is_err());
+    assert_eq!(GuessRange::new(5u32, 4), Err(InvalidRange));
+}
+
+#[test]
+fn tracks_attempts_and_status() {
+    let mut game = Game::with_secret(30).with_max_attempts(Some(3));
+    game.guess(50);
+    assert_eq!((game.attempts(), game.attempts_left(), game.status()), (1, Some(2), Status::Playing));
+    game.guess(30);
+    assert_eq!(game.status(), Status::Won);
+    game.guess(30);
+    assert_eq!(game.attempts(), 2);
+}
+
+#[test]
+fn score_rewards_binary_search() {
+    assert_eq!(score::optimal_attempts(100), 7);
+    assert_eq!(score::optimal_attempts(1_000_000), 20);
+    assert_eq!(score::score(100, 7), score::score(100, 1));
+    assert_eq!(score::score(100, 14), score::score(100, 7) / 2);
+    assert!(score::score(1_000, 10) > score::score(100, 7));
+}
+
+#[test]
+fn history_records_counted_guesses() {
+    let mut game = Game::with_secret(30);
+    game.guess(50);
+    game.guess(30);
+    game.guess(1);  // after the win, not counted
+    assert_eq!(game.history(), &[(50, Outcome::High), (30, Outcome::Win)]);
+}
+
+#[test]
+fn candidates_shrink_with_feedback() {
+    let mut game = Game::with_secret(30);
+    assert_eq!(game.candidates(), GuessRange::new(1, 100).unwrap());
+    game.guess(50);
+    game.guess(20);
+    game.guess(60);  // redundant, changes nothing
+    assert_eq!(game.candidates(), GuessRange::new(21, 49).unwrap());
+    game.guess(30);
+    assert_eq!(game.candidates(), GuessRange::new(30, 30).unwrap());
+}
+
+#[test]
+fn countdown_ends_the_game_and_freezes_the_clock() {
+    let mut game = Game::with_secret(42).with_time_limit(Some(Duration::from_millis(20)));
+    assert_eq!(game.elapsed(), Duration::ZERO);
+    assert_eq!(game.deadline(), None);  // the clock starts with the first prompt
+
+    game.start_clock();
+    assert_eq!(game.guess(10), Outcome::Low);
+    assert_eq!(game.guess_times().len(), 1);
+    thread::sleep(Duration::from_millis(30));
+
+    assert_eq!(game.guess(42), Outcome::Win);  // answered, but too late to count
+    assert_eq!(game.status(), Status::OutOfTime);
+    assert_eq!(game.attempts(), 1);
+    assert_eq!(game.time_left(), Some(Duration::ZERO));
+    let stopped = game.elapsed();
+    thread::sleep(Duration::from_millis(5));
+    assert_eq!(game.elapsed(), stopped);
+}
+
+#[test]
+fn untimed_games_never_run_out() {
+    let mut game = Game::with_secret(42);
+    game.start_clock();
+    game.check_time();
+    assert_eq!(game.time_left(), None);
+    assert_eq!(game.status(), Status::Playing);
+    game.guess(42);
+    assert!(game.elapsed() > Duration::ZERO);
+}
+
+#[test]
+fn lying_oracle_stays_within_its_budget() {
+    let mut total_lies = 0;
+    for seed in 0..50 {
+        let mut game = Game::with_secret(50).with_lies(2, seed);
+        for guess in [10, 90, 20, 80, 30, 70, 40, 60] {
+            let outcome = game.guess(guess);
+            let truth = if guess < 50 { Outcome::Low } else { Outcome::High };
+            let lied = game.lies().contains(&(game.attempts() as usize - 1));
+            assert_eq!(outcome == truth, !lied);
+        }
+        assert!(game.lies().len() <= 2);
+        assert_eq!(game.candidates(), GuessRange::new(41, 59).unwrap(), "candidates ignore lies");
+        assert_eq!(game.guess(50), Outcome::Win, "a correct guess is never denied");
+        total_lies += game.lies().len();
+    }
+    assert!(total_lies > 0);
+    assert!(Game::with_secret(50).with_lies(0, 1).lies().is_empty());
+}
@@ -0,0 +1,53 @@
+use guessing_game::hint::{self, Clue, Hint, HintError};
+use guessing_game::{Game, GuessRange, Status};
+
+#[test]
+fn clues_come_from_the_secret() {
+    let mut game = Game::with_secret(84);
+    assert_eq!(game.hint(Hint::Parity), Ok((Clue::Parity { even: true }, true)));
+    assert_eq!(game.hint(Hint::Divisors), Ok((Clue::Divisors(vec![2, 3, 4, 6, 7]), true)));
+    assert_eq!(game.hint(Hint::DigitSum), Ok((Clue::DigitSum(12), true)));
+    assert_eq!(game.hint(Hint::Within10), Err(HintError::NoGuessYet));
+    game.guess(80);
+    assert_eq!(game.hint(Hint::Within10), Ok((Clue::Within10 { guess: 80, within: true }, true)));
+    assert_eq!(game.penalty(), 1 + 2 + 2 + 1);
+}
+
+#[test]
+fn interval_hints_halve_the_candidates() {
+    let mut game = Game::with_secret(77);
+    game.guess(20);
+    let Ok((Clue::Interval(interval), true)) = game.hint(Hint::Interval) else { panic!("expected an interval") };
+    assert_eq!(interval, GuessRange::new(61, 100).unwrap());
+    assert_eq!(game.candidates(), interval);
+    assert_eq!(game.hint(Hint::Interval), Ok((Clue::Interval(GuessRange::new(61, 80).unwrap()), true)));
+    assert_eq!(game.penalty(), 6);
+
+    let mut game = Game::with_secret_in(GuessRange::new(-10i64, 10).unwrap(), -3);
+    assert_eq!(game.hint(Hint::Interval), Ok((Clue::Interval(GuessRange::new(-10, 0).unwrap()), true)));
+    assert_eq!(game.hint(Hint::DigitSum), Ok((Clue::DigitSum(3), true)));
+}
+
+#[test]
+fn penalties_count_against_the_score() {
+    let mut plain = Game::with_secret(50);
+    plain.guess(50);
+    let mut helped = Game::with_secret(50);
+    helped.hint(Hint::Parity).unwrap();
+    assert_eq!(helped.hint(Hint::Parity), Ok((Clue::Parity { even: true }, false)));  // repeating a clue is free
+    helped.guess(50);
+    assert_eq!((helped.penalty(), helped.total_attempts()), (1, 2));
+    assert_eq!(plain.score(), helped.score());  // 2 attempts is still better than optimal
+
+    assert_eq!(helped.status(), Status::Won);
+    assert_eq!(helped.hint(Hint::Parity), Err(HintError::GameOver));
+}
+
+#[test]
+fn parses_prompt_requests() {
+    assert_eq!(hint::parse_request("42\n"), None);
+    assert_eq!(hint::parse_request("hint Digit-Sum\n"), Some(Ok(Hint::DigitSum)));
+    let usage = hint::parse_request("hint\n").unwrap().unwrap_err();
+    assert!(usage.contains("parity (+1)") && usage.contains("interval (+3)"));
+    assert!(hint::parse_request("hint nope").unwrap().unwrap_err().starts_with("unknown hint `nope`; "));
+}
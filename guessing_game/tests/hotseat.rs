@@ -0,0 +1,80 @@
+use std::io::Cursor;
+use guessing_game::hotseat::{HotSeat, PlayersError, Variant};
+use guessing_game::{rng, GuessRange, Outcome, Session, Status};
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+fn hotseat(players: &[&str], variant: Variant, max_attempts: Option<u32>) -> HotSeat {
+    HotSeat::new(names(players), GuessRange::default(), variant, max_attempts, &mut rng::seeded(11)).unwrap()
+}
+
+#[test]
+fn validates_players() {
+    let range = GuessRange::default();
+    let err = HotSeat::new(names(&["solo"]), range, Variant::Shared, None, &mut rng::seeded(1)).unwrap_err();
+    assert_eq!(err, PlayersError::Count(1));
+    let nine = (0..9).map(|i| format!("p{i}")).collect();
+    assert_eq!(HotSeat::new(nine, range, Variant::Shared, None, &mut rng::seeded(1)).unwrap_err(), PlayersError::Count(9));
+    let err = HotSeat::new(names(&["Ann", "Bob", "Ann"]), range, Variant::Race, None, &mut rng::seeded(1)).unwrap_err();
+    assert_eq!(err, PlayersError::DuplicateName("Ann".to_string()));
+}
+
+#[test]
+fn shared_secret_first_finder_wins() {
+    let mut hs = hotseat(&["Ann", "Bob", "Cy"], Variant::Shared, None);
+    let secret = hs.game(0).secret();
+    assert!((0..3).all(|p| hs.game(p).secret() == secret));
+
+    assert_eq!(hs.turn(), 0);
+    hs.guess(if secret == 1 { 2 } else { 1 });
+    assert_eq!(hs.turn(), 1);
+    assert_eq!(hs.guess(secret), Outcome::Win);
+    assert!(hs.is_over());
+    assert_eq!(hs.winners(), vec![1]);
+    assert_eq!((hs.game(0).attempts(), hs.game(1).attempts(), hs.game(2).attempts()), (1, 1, 0));
+}
+
+#[test]
+fn turns_skip_players_who_are_out() {
+    let mut hs = hotseat(&["Ann", "Bob"], Variant::Shared, Some(1));
+    let miss = if hs.game(0).secret() == 1 { 2 } else { 1 };
+    hs.guess(miss);
+    assert_eq!(hs.game(0).status(), Status::Lost);
+    assert_eq!(hs.turn(), 1);
+    hs.guess(miss);
+    assert!(hs.is_over());
+    assert!(hs.winners().is_empty());
+}
+
+#[test]
+fn race_fewest_attempts_wins() {
+    let mut hs = hotseat(&["Ann", "Bob"], Variant::Race, None);
+    let (a, b) = (hs.game(0).secret(), hs.game(1).secret());
+    hs.guess(if a == 1 { 2 } else { 1 });  // Ann misses
+    hs.guess(b);  // Bob done in one
+    assert!(!hs.is_over());
+    assert_eq!(hs.turn(), 0);
+    hs.guess(a);
+    assert!(hs.is_over());
+    assert_eq!(hs.winners(), vec![1]);
+}
+
+#[test]
+fn session_transcript() {
+    let range = GuessRange::new(1u32, 10).unwrap();
+    let mut hs = HotSeat::new(names(&["Ann", "Bob"]), range, Variant::Shared, None, &mut rng::seeded(2)).unwrap();
+    let secret = hs.game(0).secret();
+    let miss = if secret == 1 { 10 } else { 1 };
+    let input = format!("{miss}\nx\n{secret}\n");
+
+    let mut session = Session::new(Cursor::new(input), Vec::new());
+    session.play_hotseat(&mut hs).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert!(output.starts_with("Ann, Bob: guess the number (1-10)!\nAnn's turn (attempt 1):\n"));
+    assert!(output.contains("Bob's turn (attempt 1):\n`x` is not a number, try again.\nBob's turn (attempt 1):\n"));
+    assert!(output.ends_with(&format!(
+        "Bob found it!\nThe number was {secret}.\nAnn: 1 attempts, still guessing\nBob: 1 attempts, found it\nBob wins!\n"
+    )));
+}
@@ -0,0 +1,82 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use serde_json::{json, Value};
+use guessing_game::http::ApiServer;
+
+fn start() -> String {
+    let server = ApiServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    thread::spawn(move || server.run());
+    addr
+}
+
+fn call(addr: &str, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "{method} {path} HTTP/1.1\r\nHost: test\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn plays_a_game_over_http() {
+    let addr = start();
+    let (status, game) = call(&addr, "POST", "/games", r#"{"difficulty": "easy", "seed": 3}"#);
+    assert_eq!(status, 201);
+    assert_eq!((game["min"].clone(), game["max"].clone(), game["status"].clone()), (json!(1), json!(10), json!("playing")));
+    assert_eq!(game["secret"], Value::Null);
+    let id = game["id"].as_u64().unwrap();
+
+    let (mut lo, mut hi) = (1, 10);
+    let secret = loop {
+        let mid = (lo + hi) / 2;
+        let (status, reply) = call(&addr, "POST", &format!("/games/{id}/guesses"), &format!(r#"{{"guess": {mid}}}"#));
+        assert_eq!(status, 200);
+        match reply["outcome"].as_str().unwrap() {
+            "low" => lo = mid + 1,
+            "high" => hi = mid - 1,
+            "win" => break mid,
+            other => panic!("unexpected outcome {other}"),
+        }
+    };
+
+    let (status, state) = call(&addr, "GET", &format!("/games/{id}"), "");
+    assert_eq!(status, 200);
+    assert_eq!(state["status"], "won");
+    assert_eq!(state["secret"], secret);
+    assert_eq!(state["guesses"].as_array().unwrap().len() as u64, state["attempts"].as_u64().unwrap());
+
+    let (status, _) = call(&addr, "POST", &format!("/games/{id}/guesses"), r#"{"guess": 1}"#);
+    assert_eq!(status, 409);
+}
+
+#[test]
+fn same_seed_same_secret() {
+    let addr = start();
+    let body = r#"{"min": -50, "max": 50, "seed": 9, "max_attempts": 1}"#;
+    let (_, a) = call(&addr, "POST", "/games", body);
+    let (_, b) = call(&addr, "POST", "/games", body);
+    let reveal = |id: &Value| call(&addr, "POST", &format!("/games/{id}/guesses"), r#"{"guess": 0}"#).1;
+    let (a, b) = (reveal(&a["id"]), reveal(&b["id"]));
+    assert_eq!(a["attempts_left"], 0);
+    assert_eq!(a["secret"], b["secret"]);
+}
+
+#[test]
+fn reports_errors() {
+    let addr = start();
+    assert_eq!(call(&addr, "GET", "/games/42", "").0, 404);
+    assert_eq!(call(&addr, "GET", "/nowhere", "").0, 404);
+    assert_eq!(call(&addr, "DELETE", "/games", "").0, 405);
+    assert_eq!(call(&addr, "POST", "/games", "{nope").0, 400);
+    assert_eq!(call(&addr, "POST", "/games", r#"{"difficulty": "nightmare"}"#).0, 422);
+    assert_eq!(call(&addr, "POST", "/games", r#"{"min": 5, "max": 1}"#).0, 422);
+
+    let (_, game) = call(&addr, "POST", "/games", "");
+    let (status, reply) = call(&addr, "POST", &format!("/games/{}/guesses", game["id"]), r#"{"guess": 500}"#);
+    assert_eq!(status, 422);
+    assert_eq!(reply["error"], "500 is outside 1-100");
+}
@@ -0,0 +1,72 @@
+use std::io::{self, BufRead, Cursor, Read};
+use std::time::{Duration, Instant};
+use guessing_game::input::TimedReader;
+use guessing_game::{Game, Session, Status};
+
+/// Never yields a line, like a player who stops typing.
+struct Silent;
+
+impl Read for Silent {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        std::thread::sleep(Duration::from_secs(3600));
+        Ok(0)
+    }
+}
+
+impl BufRead for Silent {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        std::thread::sleep(Duration::from_secs(3600));
+        Ok(&[])
+    }
+
+    fn consume(&mut self, _: usize) {}
+}
+
+#[test]
+fn passes_lines_through_until_eof() {
+    let mut reader = TimedReader::new(Cursor::new("1\n22\n"));
+    let lines: Vec<String> = (&mut reader).lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["1", "22"]);
+
+    let mut reader = reader.with_deadline(Some(Instant::now() + Duration::from_millis(30)));
+    assert_eq!(reader.read_line(&mut String::new()).unwrap(), 0, "end of input stays ended");
+}
+
+#[test]
+fn reads_time_out_at_the_deadline() {
+    let start = Instant::now();
+    let mut reader = TimedReader::new(Silent).with_deadline(Some(start + Duration::from_millis(30)));
+    let err = reader.read_line(&mut String::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(30));
+}
+
+#[test]
+fn interrupting_ends_input_until_the_reader_is_gone() {
+    let mut reader = TimedReader::new(Silent);
+    let interrupter = reader.interrupter();
+    assert!(interrupter.interrupt());
+    assert_eq!(reader.read_line(&mut String::new()).unwrap(), 0);
+    assert_eq!(reader.read_line(&mut String::new()).unwrap(), 0);
+    drop(reader);
+    assert!(!interrupter.interrupt());
+}
+
+#[test]
+fn countdown_ends_a_session_mid_prompt() {
+    let mut game = Game::with_secret(42).with_time_limit(Some(Duration::from_millis(50)));
+    game.start_clock();
+    let input = TimedReader::new(Silent).with_deadline(game.deadline());
+    let mut session = Session::new(input, Vec::new());
+    session.play(&mut game).unwrap();
+
+    assert_eq!(game.status(), Status::OutOfTime);
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert_eq!(
+        output,
+        "Guess the number (1-100)!\n\
+         You have 0.05 seconds.\n\
+         Time's up, the number was 42.\n\
+         Attempts: 0, score: 0\n"
+    );
+}
@@ -0,0 +1,102 @@
+use std::thread;
+use guessing_game::net::{Client, Server};
+use guessing_game::GuessRange;
+
+fn start_server() -> String {
+    let range = GuessRange::new(1i64, 100).unwrap();
+    let server = Server::bind("127.0.0.1:0", range, Some(5)).unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    thread::spawn(move || server.run());
+    addr
+}
+
+fn joined(addr: &str, name: &str) -> Client {
+    let mut client = Client::connect(addr).unwrap();
+    assert_eq!(client.request(&format!("JOIN {name}")).unwrap().unwrap(), format!("WELCOME {name}"));
+    client
+}
+
+fn reply(client: &mut Client, line: &str) -> String {
+    client.request(line).unwrap().unwrap()
+}
+
+/// Binary search over the protocol; returns the secret.
+fn solve(client: &mut Client) -> i64 {
+    let (mut lo, mut hi) = (1, 100);
+    loop {
+        let mid = lo + (hi - lo) / 2;
+        let answer = reply(client, &format!("GUESS {mid}"));
+        match answer.split_whitespace().next().unwrap() {
+            "LOW" => lo = mid + 1,
+            "HIGH" => hi = mid - 1,
+            "WIN" => return mid,
+            other => panic!("unexpected {other}"),
+        }
+    }
+}
+
+#[test]
+fn requires_join_and_unique_names() {
+    let addr = start_server();
+    let mut anon = Client::connect(&addr).unwrap();
+    assert_eq!(reply(&mut anon, "ROOMS"), "ERR JOIN with a name first");
+    assert_eq!(reply(&mut anon, "DANCE"), "ERR unknown command DANCE");
+
+    let _ann = joined(&addr, "ann");
+    assert_eq!(reply(&mut anon, "JOIN ann"), "ERR name ann is taken");
+    assert_eq!(reply(&mut anon, "GUESS 5"), "ERR JOIN with a name first");
+}
+
+#[test]
+fn rooms_share_a_secret_and_broadcast() {
+    let addr = start_server();
+    let mut ann = joined(&addr, "ann");
+    let mut bob = joined(&addr, "bob");
+
+    assert_eq!(reply(&mut ann, "GUESS 5"), "ERR enter a room first");
+    assert_eq!(reply(&mut ann, "CREATE den"), "ENTERED den 1 100");
+    assert_eq!(reply(&mut bob, "CREATE den"), "ERR room den exists");
+    assert_eq!(reply(&mut bob, "ROOMS"), "ROOMS den:1");
+    assert_eq!(reply(&mut bob, "ENTER den"), "ENTERED den 1 100");
+    assert_eq!(ann.recv().unwrap().unwrap(), "EVENT den JOINED bob");
+
+    assert_eq!(reply(&mut bob, "GUESS 500"), "ERR 500 is outside 1-100");
+    let first = reply(&mut bob, "GUESS 1");
+    let event = ann.recv().unwrap().unwrap();
+    assert_eq!(event, format!("EVENT GUESS bob 1 {}", first.split_whitespace().next().unwrap()));
+
+    let secret = solve(&mut ann);
+    assert_eq!(bob.recv().unwrap().unwrap().split_whitespace().next(), Some("EVENT"));
+    assert_eq!(ann.recv().unwrap().unwrap(), "ROUND 1 100");
+    let mut last = String::new();
+    while !last.starts_with("ROUND") {
+        last = bob.recv().unwrap().unwrap();
+        if last.starts_with("EVENT WON") {
+            assert!(last.starts_with("EVENT WON ann ") && last.ends_with(&format!(" {secret}")));
+        }
+    }
+
+    assert_eq!(reply(&mut bob, "LEAVE"), "LEFT den");
+    assert_eq!(ann.recv().unwrap().unwrap(), "EVENT den LEFT bob");
+    assert_eq!(reply(&mut bob, "QUIT"), "BYE");
+}
+
+#[test]
+fn empty_rooms_close_and_rooms_are_independent() {
+    let addr = start_server();
+    let mut ann = joined(&addr, "ann");
+    let mut bob = joined(&addr, "bob");
+    assert_eq!(reply(&mut ann, "CREATE a"), "ENTERED a 1 100");
+    assert_eq!(reply(&mut bob, "CREATE b"), "ENTERED b 1 100");
+    assert_eq!(reply(&mut ann, "ROOMS"), "ROOMS a:1 b:1");
+
+    solve(&mut ann);
+    assert_eq!(ann.recv().unwrap().unwrap(), "ROUND 1 100");
+
+    drop(bob);
+    let mut rooms = reply(&mut ann, "ROOMS");
+    while rooms != "ROOMS a:1" {
+        thread::yield_now();  // bob's disconnect is handled on another thread
+        rooms = reply(&mut ann, "ROOMS");
+    }
+}
@@ -0,0 +1,88 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+use guessing_game::replay::{self, Log, Pace, Record, Recorder, Setup};
+use guessing_game::{GuessRange, Outcome, Session, Status};
+
+fn recorded(name: &str, setup: &Setup, input: &str) -> (PathBuf, Log) {
+    let path = std::env::temp_dir().join(format!("guessing_game_{name}_{}.jsonl", std::process::id()));
+    let recorder = Recorder::create(&path, setup).unwrap();
+    let mut game = setup.game::<i64>().unwrap();
+    let mut session = Session::new(Cursor::new(input), Vec::new()).with_observer(recorder.observer());
+    session.play(&mut game).unwrap();
+    recorder.finish().unwrap();
+    let log = Log::load(&path).unwrap();
+    (path, log)
+}
+
+#[test]
+fn same_setup_same_game() {
+    let setup = Setup::new(GuessRange::new(1_i64, 1000).unwrap(), 42).with_lies(2);
+    let (a, b) = (setup.game::<i64>().unwrap(), setup.game::<i64>().unwrap());
+    assert_eq!(a.secret(), b.secret());
+    assert_eq!(a.lie_seed(), b.lie_seed());
+    assert!(setup.game::<u64>().is_ok());
+    assert!(Setup::new(GuessRange::new(-5_i64, 5).unwrap(), 1).game::<u64>().is_err());
+}
+
+#[test]
+fn records_and_verifies_a_game() {
+    let setup = Setup::new(GuessRange::new(1_i64, 100).unwrap(), 7);
+    let secret = setup.game::<i64>().unwrap().secret();
+    let input = format!("50\nhint parity\n{secret}\n");
+    let (path, log) = recorded("verified", &setup, &input);
+
+    assert_eq!(log.setup, setup);
+    assert!(matches!(log.records[0], Record::Guess { guess: 50, .. }));
+    assert!(matches!(log.records[1], Record::Hint { .. }));
+    assert!(matches!(log.records[3], Record::End { status: Status::Won, attempts: 2, .. }));
+    assert!(log.is_complete());
+    assert_eq!(log.verify::<i64>(), Ok(()));
+
+    let mut output = Vec::new();
+    replay::play_back(&log, Cursor::new(""), &mut output, Pace::Timed(0.0)).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("Replaying a game on 1 to 100, seed 7.\n"), "{output}");
+    assert!(output.contains("] parity hint: the number is "), "{output}");
+    assert!(output.ends_with(&format!("Won after 2 attempts; the number was {secret}.\n")), "{output}");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn repeated_hints_verify() {
+    let setup = Setup::new(GuessRange::new(1_i64, 100).unwrap(), 5).with_lies(2);
+    let (path, log) = recorded("repeated_hints", &setup, "50\nhint parity\nhint interval\nhint parity\n25\n");
+    let hints: Vec<&Record> = log.records.iter().filter(|r| matches!(r, Record::Hint { .. })).collect();
+    assert_eq!(hints.len(), 2, "{:?}", log.records);
+    assert_eq!(log.verify::<i64>(), Ok(()));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn catches_doctored_answers() {
+    let setup = Setup::new(GuessRange::new(1_i64, 100).unwrap(), 3).with_lies(1);
+    let (path, mut log) = recorded("doctored", &setup, "50\n25\n75\n");
+    assert!(matches!(log.records[3], Record::End { status: Status::GaveUp, attempts: 3, .. }));
+    assert_eq!(log.verify::<i64>(), Ok(()));
+
+    if let Record::Guess { outcome, .. } = &mut log.records[1] {
+        *outcome = if *outcome == Outcome::Low { Outcome::High } else { Outcome::Low };
+    }
+    let err = log.verify::<i64>().unwrap_err();
+    assert!(err.starts_with("guess 2 (25): the log says"), "{err}");
+
+    log.records.truncate(1);
+    log.records.push(Record::End { at_ms: 0, status: Status::Won, attempts: 1, score: 100, secret: 50 });
+    assert!(log.verify::<i64>().is_err());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn rejects_malformed_logs() {
+    assert!(Log::read(Cursor::new("")).is_err());
+    assert!(Log::read(Cursor::new("{\"guess\":{\"at_ms\":0,\"guess\":5,\"outcome\":\"low\"}}\n")).is_err());
+    let start = serde_json::to_string(&Record::Start(Setup::new(GuessRange::new(1_i64, 10).unwrap(), 1))).unwrap();
+    let err = Log::read(Cursor::new(format!("{start}\n\nnot json\n"))).unwrap_err();
+    assert!(err.to_string().starts_with("line 3:"), "{err}");
+    assert_eq!(Log::read(Cursor::new(format!("{start}\n"))).unwrap().records, []);
+}
@@ -0,0 +1,65 @@
+use std::cmp::Ordering;
+use std::io::Cursor;
+use guessing_game::reverse::{self, Inconsistent, Solver, Verdict};
+use guessing_game::{GuessRange, Session};
+
+fn reverse_transcript(range: GuessRange, input: &str) -> (Verdict, String) {
+    let mut session = Session::new(Cursor::new(input), Vec::new());
+    let verdict = session.play_reverse(range).unwrap();
+    (verdict, String::from_utf8(session.into_inner().1).unwrap())
+}
+
+#[test]
+fn binary_search_finds_any_secret_within_optimal_attempts() {
+    let range = GuessRange::new(1u32, 100).unwrap();
+    for secret in 1..=100u32 {
+        let mut solver = Solver::new(range);
+        while let Some(guess) = solver.next_guess() {
+            solver.answer(guess, guess.cmp(&secret)).unwrap();
+        }
+        assert_eq!(solver.found(), Some(secret));
+        assert!(solver.attempts() <= 7);
+    }
+}
+
+#[test]
+fn plays_against_a_human() {
+    let (verdict, output) = reverse_transcript(GuessRange::default(), "h\nwhat\nlow\nc\n");
+    assert_eq!(verdict, Verdict::Solved { secret: 37, attempts: 3 });
+    assert_eq!(
+        output,
+        "Think of a number (1-100) and I'll guess it.\n\
+         Answer low, high or correct (l/h/c).\n\
+         Is it 50?\n\
+         Is it 25?\n\
+         please answer low, high or correct, try again:\n\
+         Is it 37?\n\
+         Got it: 37 in 3 attempts!\n"
+    );
+}
+
+#[test]
+fn catches_contradictory_answers() {
+    let range = GuessRange::new(1u32, 100).unwrap();
+    let mut solver = Solver::new(range);
+    solver.answer(40, Ordering::Less).unwrap();
+    let cheat = solver.answer(41, Ordering::Greater).unwrap_err();
+    assert_eq!(cheat, Inconsistent { too_low: Some(40), too_high: Some(41), range });
+    assert_eq!(cheat.to_string(), "you said 40 was too low but 41 was too high");
+}
+
+#[test]
+fn reports_cheating_at_the_edge_of_the_range() {
+    let range = GuessRange::new(1u32, 3).unwrap();
+    let (verdict, output) = reverse_transcript(range, "l\nl\n");
+    assert!(matches!(verdict, Verdict::Cheating(_)));
+    assert!(output.ends_with("Cheater! you said 3 was too low but the numbers stop at 3.\n"));
+}
+
+#[test]
+fn parses_answers() {
+    assert_eq!(reverse::parse_answer(" Low "), Some(Ordering::Less));
+    assert_eq!(reverse::parse_answer("H"), Some(Ordering::Greater));
+    assert_eq!(reverse::parse_answer("correct"), Some(Ordering::Equal));
+    assert_eq!(reverse::parse_answer("maybe"), None);
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::time::Duration;
+use guessing_game::hint::Hint;
+use guessing_game::save::{self, Move, SaveSlot, SavedGame};
+use guessing_game::{Game, GuessRange, Status};
+
+#[test]
+fn restores_guesses_hints_and_settings() {
+    let mut game = Game::with_secret(37).with_max_attempts(Some(10)).with_elapsed(Duration::from_millis(1500));
+    game.hint(Hint::Parity).unwrap();
+    game.guess(50);
+    game.hint(Hint::Divisors).unwrap();
+    game.guess(25);
+
+    let saved = SavedGame::capture(&game, Some(9));
+    assert_eq!(saved.moves, [Move::Hint(Hint::Parity), Move::Guess(50), Move::Hint(Hint::Divisors), Move::Guess(25)]);
+    assert_eq!((saved.seed, saved.max_attempts, saved.elapsed_ms), (Some(9), Some(10), 1500));
+
+    let restored: Game = saved.restore().unwrap();
+    assert_eq!(restored.secret(), 37);
+    assert_eq!(restored.history(), game.history());
+    assert_eq!(restored.hints(), game.hints());
+    assert_eq!(restored.penalty(), game.penalty());
+    assert_eq!(restored.attempts_left(), Some(8));
+    assert!(restored.elapsed() >= Duration::from_millis(1500));
+}
+
+#[test]
+fn lies_replay_the_same_way() {
+    let mut game = Game::with_secret(37).with_lies(3, 11);
+    for guess in [50, 25, 40, 30, 35] {
+        game.guess(guess);
+    }
+    let restored: Game = SavedGame::capture(&game, None).restore().unwrap();
+    assert_eq!(restored.history(), game.history());
+    assert_eq!(restored.lies(), game.lies());
+}
+
+#[test]
+fn feedback_is_saved_with_the_game() {
+    let mut game = Game::with_secret(37);
+    game.guess(50);
+    let saved = SavedGame::capture(&game, None).with_feedback("distance");
+    let mut json: serde_json::Value = serde_json::to_value(&saved).unwrap();
+    assert_eq!(json["feedback"], "distance");
+
+    json.as_object_mut().unwrap().remove("feedback");
+    let older: SavedGame = serde_json::from_value(json).unwrap();
+    assert_eq!(older.feedback, "low-high");
+    let err = saved.with_feedback("psychic").restore::<i64>().unwrap_err();
+    assert!(err.contains("unknown feedback"), "{err}");
+}
+
+#[test]
+fn the_secret_is_not_stored_in_plain_sight() {
+    let game = Game::in_range(GuessRange::new(1_000_000_i64, 9_999_999).unwrap(), &mut rand::thread_rng());
+    let json = serde_json::to_string(&SavedGame::capture(&game, None)).unwrap();
+    assert!(!json.contains(&game.secret().to_string()));
+}
+
+#[test]
+fn rejects_tampered_and_finished_games() {
+    let mut game = Game::with_secret(37);
+    game.guess(50);
+    let mut json: serde_json::Value = serde_json::to_value(SavedGame::capture(&game, None)).unwrap();
+    json["secret"] = "not hex".into();
+    let tampered: SavedGame = serde_json::from_value(json).unwrap();
+    assert!(tampered.restore::<i64>().is_err());
+
+    game.guess(37);
+    assert_eq!(game.status(), Status::Won);
+    let err = SavedGame::capture(&game, None).restore::<i64>().unwrap_err();
+    assert!(err.contains("already over"), "{err}");
+
+    let wide = SavedGame::capture(&Game::<u64>::with_secret_in(GuessRange::new(0, u64::MAX).unwrap(), u64::MAX), None);
+    assert!(wide.restore::<i64>().is_err());
+    assert_eq!(wide.restore::<u64>().unwrap().secret(), u64::MAX);
+}
+
+#[test]
+fn slots_are_found_and_discarded() {
+    let dir = std::env::temp_dir().join(format!("guessing_game_saves_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(save::latest(&dir).unwrap(), None);
+
+    let mut game = Game::with_secret(37);
+    game.guess(50);
+    let slot = SaveSlot::new_in(&dir, None);
+    slot.write(&game).unwrap();
+    assert_eq!(save::latest(&dir).unwrap(), Some(slot.path.clone()));
+    let restored: Game = SavedGame::load(&slot.path).unwrap().restore().unwrap();
+    assert_eq!(restored.history(), game.history());
+
+    slot.discard().unwrap();
+    slot.discard().unwrap();  // already gone
+    assert_eq!(save::latest(&dir).unwrap(), None);
+    fs::remove_dir_all(&dir).unwrap();
+}
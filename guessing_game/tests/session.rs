@@ -0,0 +1,245 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use guessing_game::events::GameEvent;
+use guessing_game::hint::Hint;
+use guessing_game::{feedback, Game, GuessError, GuessRange, Session, Status};
+
+fn transcript(game: &mut Game, input: &str) -> String {
+    let mut session = Session::new(Cursor::new(input), Vec::new());
+    session.play(game).unwrap();
+    String::from_utf8(session.into_inner().1).unwrap()
+}
+
+#[test]
+fn replays_guesses_until_win() {
+    let output = transcript(&mut Game::with_secret(37), "50\n25\n37\n");
+    assert_eq!(
+        output,
+        "Guess the number (1-100)!\nToo high!\nToo low!\nYou win!\nAttempts: 3, score: 664\n"
+    );
+}
+
+#[test]
+fn explains_bad_input_and_reprompts() {
+    let output = transcript(&mut Game::with_secret(5), "five\n\n5000\n  5  \n");
+    assert_eq!(
+        output,
+        "Guess the number (1-100)!\n\
+         `five` is not a number, try again:\n\
+         no guess entered, try again:\n\
+         5000 is outside 1-100, try again:\n\
+         You win!\n\
+         Attempts: 1, score: 664\n"
+    );
+}
+
+#[test]
+fn stops_reading_after_win() {
+    let mut game = Game::with_secret(1);
+    let mut session = Session::new(Cursor::new("1\n2\n"), Vec::new());
+    session.play(&mut game).unwrap();
+    let (input, _) = session.into_inner();
+    assert_eq!(input.position(), 2);
+}
+
+#[test]
+fn eof_gives_up_and_reveals_secret() {
+    let mut game = Game::with_secret(99);
+    let output = transcript(&mut game, "1\n");
+    assert_eq!(
+        output,
+        "Guess the number (1-100)!\nToo low!\nYou gave up, the number was 99.\nAttempts: 1, score: 0\n"
+    );
+    assert_eq!(game.status(), Status::GaveUp);
+}
+
+#[test]
+fn running_out_of_attempts_loses() {
+    let mut game = Game::with_secret(42).with_max_attempts(Some(2));
+    let output = transcript(&mut game, "10\n90\n42\n");
+    assert_eq!(
+        output,
+        "Guess the number (1-100)!\n\
+         Too low! (1 left)\n\
+         Too high!\n\
+         Out of attempts, the number was 42.\n\
+         Attempts: 2, score: 0\n"
+    );
+    assert_eq!(game.status(), Status::Lost);
+}
+
+#[test]
+fn read_failures_are_reported_as_io_errors() {
+    let mut game = Game::with_secret(1);
+    let mut session = Session::new(Cursor::new(vec![0xff, b'\n']), Vec::new());
+    assert!(matches!(session.play(&mut game), Err(GuessError::Io(_))));
+}
+
+#[test]
+fn prompt_and_validation_follow_the_range() {
+    let range = GuessRange::new(-10i64, 10).unwrap();
+    let mut game = Game::with_secret_in(range, -3);
+    let mut session = Session::new(Cursor::new("50\n-5\n-3\n"), Vec::new());
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert_eq!(
+        output,
+        "Guess the number (-10 to 10)!\n\
+         50 is outside -10 to 10, try again:\n\
+         Too low!\n\
+         You win!\n\
+         Attempts: 2, score: 439\n"
+    );
+}
+
+#[test]
+fn stopwatch_shows_splits_and_final_time() {
+    let mut game = Game::with_secret(42);
+    let mut session = Session::new(Cursor::new("50\n42\n"), Vec::new()).with_stopwatch();
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines[1].starts_with("Too high! (0.0") && lines[1].ends_with("s)"), "{output}");
+    assert!(lines[2].starts_with("You win! (0.0"));
+    assert!(lines[4].starts_with("Time: 0.0"));
+}
+
+#[test]
+fn hints_at_the_prompt_add_to_the_attempt_count() {
+    let mut game = Game::with_secret(42);
+    let mut session = Session::new(Cursor::new("hint parity\nhint within-10\nhint bogus\n42\n"), Vec::new());
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[1], "Hint: the number is even (+1 attempts so far).");
+    assert_eq!(lines[2], "No hint: make a guess first.");
+    assert!(lines[3].starts_with("unknown hint `bogus`; hints cost extra attempts: parity (+1)"));
+    assert_eq!(lines[4], "You win!");
+    assert_eq!(lines[5], format!("Attempts: 1 + 1 for hints, score: {}", game.score()));
+}
+
+#[test]
+fn repeated_hints_are_free_and_not_reported_again() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let mut game = Game::with_secret(42);
+    let mut session = Session::new(Cursor::new("50\nhint parity\nhint interval\nhint parity\n42\n"), Vec::new())
+        .with_observer(move |event| sink.lock().unwrap().push(event.clone()));
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert!(output.contains("Hint: the number is even (+1 attempts so far).\nHint: the number is in 25-49 (+4 attempts so far).\nHint: the number is even (+4 attempts so far).\n"), "{output}");
+
+    let hints: Vec<GameEvent> = events.lock().unwrap().iter().filter(|e| matches!(e, GameEvent::Hint { .. })).cloned().collect();
+    assert_eq!(hints, [
+        GameEvent::Hint { hint: Hint::Parity, clue: "the number is even".to_string(), penalty: 1 },
+        GameEvent::Hint { hint: Hint::Interval, clue: "the number is in 25-49".to_string(), penalty: 4 },
+    ]);
+}
+
+#[test]
+fn feedback_models_replace_low_high() {
+    let mut game = Game::with_secret(42).with_max_attempts(Some(5));
+    let feedback = guessing_game::feedback::by_name("warmer-colder").unwrap();
+    let mut session = Session::new(Cursor::new("10\n30\n90\n42\n"), Vec::new()).with_feedback(feedback);
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    let lines: Vec<&str> = output.lines().skip(1).take(4).collect();
+    assert_eq!(lines, [
+        "Missed! Guess again to find out if you're getting warmer. (4 left)",
+        "Warmer! (3 left)",
+        "Colder! (2 left)",
+        "You win!",
+    ]);
+}
+
+#[test]
+fn lying_games_confess_at_the_end() {
+    let mut game = Game::with_secret(50).with_lies(3, 11);
+    let mut session = Session::new(Cursor::new("10\n90\n20\n80\n30\n70\n50\n"), Vec::new());
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert!(output.contains("Careful: I may lie up to 3 times about low and high.\n"));
+    assert_eq!(game.lies(), [1, 4]);
+    let answers: Vec<&str> = output.lines().filter(|line| line.starts_with("Too ")).collect();
+    assert_eq!(answers, ["Too low!", "Too low!", "Too low!", "Too high!", "Too high!", "Too high!"]);
+    assert!(output.contains("I lied about guesses 2 (90), 5 (30).\n"), "{output}");
+}
+
+#[test]
+fn code_mode_reuses_the_guess_loop() {
+    let rules = guessing_game::code::Rules::default();
+    let mut game = guessing_game::code::CodeGame::with_secret(rules, rules.parse("5271").unwrap());
+    let mut session = Session::new(Cursor::new("1234\n1x34\n5271\n"), Vec::new());
+    session.play_code(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert_eq!(
+        output,
+        "Crack the code (4 distinct digits)!\n\
+         1 bull, 1 cow\n\
+         `x` is not a digit, try again:\n\
+         You cracked it!\n\
+         Attempts: 2\n"
+    );
+}
+
+#[test]
+fn word_mode_reuses_the_guess_loop() {
+    use guessing_game::word::{Dictionary, WordGame};
+    let mut game = WordGame::with_secret(Dictionary::builtin(), "crane");
+    let mut session = Session::new(Cursor::new("slate\ncrne\ncrane\n"), Vec::new());
+    session.play_words(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert_eq!(
+        output,
+        "Guess the 5-letter word!\n\
+         + right place, ? wrong place, - not in the word\n\
+         slate --+-+ (5 left)\n\
+         expected 5 letters, got 4, try again:\n\
+         crane +++++\n\
+         You got it in 2!\n"
+    );
+}
+
+#[test]
+fn end_of_input_saves_instead_of_giving_up() {
+    let dir = std::env::temp_dir().join(format!("guessing_game_session_saves_{}", std::process::id()));
+    let slot = guessing_game::save::SaveSlot::new_in(&dir, None);
+    let mut game = Game::with_secret(37);
+    let mut session = Session::new(Cursor::new("50\nsave\n"), Vec::new()).with_save_slot(slot.clone());
+    session.play(&mut game).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert_eq!(game.status(), Status::Playing);
+    assert!(output.contains(&format!("Game saved to {}.\n", slot.path.display())), "{output}");
+    assert!(output.ends_with("pick it up with --continue.\n"), "{output}");
+
+    let mut resumed: Game = guessing_game::save::SavedGame::load(&slot.path).unwrap().restore().unwrap();
+    let mut session = Session::new(Cursor::new("37\n"), Vec::new()).with_save_slot(slot.clone());
+    session.play(&mut resumed).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert!(output.contains("Guesses so far: 50 (Too high!)\n"), "{output}");
+    assert_eq!(resumed.status(), Status::Won);
+    assert!(!slot.path.exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn resumed_games_keep_their_feedback() {
+    let dir = std::env::temp_dir().join(format!("guessing_game_session_feedback_{}", std::process::id()));
+    let slot = guessing_game::save::SaveSlot::new_in(&dir, None).with_feedback("warmer-colder");
+    let mut game = Game::with_secret(37);
+    let mut session = Session::new(Cursor::new("50\n"), Vec::new())
+        .with_feedback(feedback::by_name("warmer-colder").unwrap())
+        .with_save_slot(slot.clone());
+    session.play(&mut game).unwrap();
+
+    let saved = guessing_game::save::SavedGame::load(&slot.path).unwrap();
+    assert_eq!(saved.feedback, "warmer-colder");
+    let mut resumed: Game = saved.restore().unwrap();
+    let mut session = Session::new(Cursor::new("40\n37\n"), Vec::new())
+        .with_feedback(feedback::by_name(&saved.feedback).unwrap());
+    session.play(&mut resumed).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+    assert!(output.contains("Guesses so far: 50 (Missed! Guess again to find out if you're getting warmer.)\nWarmer!\n"), "{output}");
+    assert!(!output.contains("too high") && !output.contains("Too high"), "{output}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
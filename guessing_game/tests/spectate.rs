@@ -0,0 +1,81 @@
+use std::io::Cursor;
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde_json::{json, Value};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+use guessing_game::events::GameEvent;
+use guessing_game::spectate::Spectators;
+use guessing_game::{Game, Session};
+
+type Socket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+fn next_event(socket: &mut Socket) -> Option<Value> {
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => return Some(serde_json::from_str(&text).unwrap()),
+            Ok(Message::Close(_)) | Err(_) => return None,
+            Ok(_) => continue,
+        }
+    }
+}
+
+#[test]
+fn session_emits_events() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let mut game = Game::with_secret(37);
+    let mut session = Session::new(Cursor::new("50\nx\n37\n"), Vec::new())
+        .with_observer(move |event| sink.lock().unwrap().push(event.clone()));
+    session.play(&mut game).unwrap();
+
+    let events = events.lock().unwrap();
+    let json: Vec<Value> = events.iter().map(|e| serde_json::to_value(e).unwrap()).collect();
+    assert_eq!(json, vec![
+        json!({"event": "created", "min": 1, "max": 100, "max_attempts": null}),
+        json!({"event": "guess", "guess": 50, "attempt": 1}),
+        json!({"event": "feedback", "guess": 50, "outcome": "high"}),
+        json!({"event": "guess", "guess": 37, "attempt": 2}),
+        json!({"event": "feedback", "guess": 37, "outcome": "win"}),
+        json!({"event": "won", "attempts": 2, "score": 664, "secret": 37}),
+    ]);
+}
+
+#[test]
+fn late_joiners_get_history_then_live_events() {
+    let spectators = Spectators::bind("127.0.0.1:0").unwrap();
+    let game = Game::with_secret(10);
+    spectators.publish(&GameEvent::created(&game));
+
+    let (mut socket, _) = tungstenite::connect(spectators.url()).unwrap();
+    assert_eq!(next_event(&mut socket).unwrap()["event"], "created");
+
+    spectators.publish(&GameEvent::Guess { guess: 5, attempt: 1 });
+    assert_eq!(next_event(&mut socket).unwrap(), json!({"event": "guess", "guess": 5, "attempt": 1}));
+
+    let (mut late, _) = tungstenite::connect(spectators.url()).unwrap();
+    assert_eq!(next_event(&mut late).unwrap()["event"], "created");
+    assert_eq!(next_event(&mut late).unwrap()["event"], "guess");
+
+    spectators.finish();
+    assert_eq!(next_event(&mut socket), None);
+    assert_eq!(next_event(&mut late), None);
+}
+
+#[test]
+fn rejects_other_paths() {
+    let spectators = Spectators::bind("127.0.0.1:0").unwrap();
+    let url = format!("ws://{}/elsewhere", spectators.local_addr());
+    assert!(tungstenite::connect(url).is_err());
+}
+
+#[test]
+fn idle_connections_do_not_hold_up_finish() {
+    let spectators = Spectators::bind("127.0.0.1:0").unwrap();
+    let _idle = TcpStream::connect(spectators.local_addr()).unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    let started = Instant::now();
+    spectators.finish();
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
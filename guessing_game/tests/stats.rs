@@ -0,0 +1,93 @@
+use std::fs;
+use guessing_game::hint::Hint;
+use guessing_game::stats::{self, Stats};
+use guessing_game::{Difficulty, Game, GuessRange};
+
+fn won_in(attempts: u32) -> Game {
+    let mut game = Game::with_secret(100);
+    for _ in 1..attempts {
+        game.guess(1);
+    }
+    game.guess(100);
+    game
+}
+
+#[test]
+fn records_wins_losses_and_histogram() {
+    let mut stats = Stats::default();
+    stats.record(&won_in(3));
+    stats.record(&won_in(5));
+    stats.record(&won_in(3));
+
+    let mut lost = Game::with_secret(100).with_max_attempts(Some(1));
+    lost.guess(1);
+    stats.record(&lost);
+    stats.record(&Game::with_secret(100));  // unfinished, ignored
+
+    let record = &stats.by_difficulty["normal"];
+    assert_eq!((record.played, record.wins, record.losses), (4, 3, 1));
+    assert_eq!(record.best_attempts, Some(3));
+    assert!((record.average_attempts().unwrap() - 11.0 / 3.0).abs() < 1e-9);
+    assert_eq!(record.attempts_to_win.get(&3), Some(&2));
+
+    let mut report = Vec::new();
+    stats.write_report(&mut report).unwrap();
+    let report = String::from_utf8(report).unwrap();
+    assert!(report.contains("normal: attempts to win"));
+    assert!(report.contains("   3 | ######################################## 2"));
+}
+
+#[test]
+fn hint_penalties_count_as_attempts() {
+    let mut stats = Stats::default();
+    let mut helped = Game::with_secret(100);
+    helped.hint(Hint::Divisors).unwrap();
+    helped.guess(100);
+    stats.record(&helped);
+
+    let record = &stats.by_difficulty["normal"];
+    assert_eq!(record.best_attempts, Some(3));
+    assert_eq!(record.attempts_to_win.get(&3), Some(&1));
+    assert_eq!(record.average_attempts(), Some(3.0));
+}
+
+#[test]
+fn categories_name_presets_and_custom_ranges() {
+    assert_eq!(stats::category(Difficulty::Insane.range::<i64>()), "insane");
+    assert_eq!(stats::category(GuessRange::new(-5i64, 5).unwrap()), "-5 to 5");
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let dir = std::env::temp_dir().join(format!("guessing_game_stats_{}", std::process::id()));
+    let path = dir.join("nested/stats.json");
+    assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+
+    let mut stats = Stats::default();
+    stats.record(&won_in(2));
+    stats.save(&path).unwrap();
+    assert_eq!(Stats::load(&path).unwrap(), stats);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn speedruns_keep_the_fastest_win() {
+    let mut stats = Stats::default();
+    let mut game = Game::with_secret(100);
+    game.start_clock();
+    game.guess(100);
+    assert!(stats.record_speedrun(&game));
+    assert!(!stats.record_speedrun(&game), "a tie is not a new best");
+    assert!(stats.by_difficulty["normal"].best_time_ms.is_some());
+
+    let mut lost = Game::with_secret(100).with_max_attempts(Some(1));
+    lost.guess(1);
+    assert!(!stats.record_speedrun(&lost));
+
+    assert_eq!(stats::format_millis(61_005), "61.005s");
+    let old = r#"{"by_difficulty": {"easy": {"played": 1, "wins": 0, "losses": 1, "win_attempts": 0,
+                  "best_attempts": null, "best_score": 0, "attempts_to_win": {}}}}"#;
+    let stats: Stats = serde_json::from_str(old).unwrap();
+    assert_eq!(stats.by_difficulty["easy"].best_time_ms, None);
+}
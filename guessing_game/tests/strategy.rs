@@ -0,0 +1,67 @@
+use guessing_game::simulate::simulate;
+use guessing_game::strategy::{self, NAMES};
+use guessing_game::{rng, score, Difficulty, Game, GuessRange, Status};
+
+#[test]
+fn every_strategy_finds_every_secret() {
+    let range = GuessRange::new(-3i64, 20).unwrap();
+    let mut moves = rng::seeded(9);
+    for name in NAMES {
+        let mut strategy = strategy::by_name(name).unwrap();
+        for secret in -3..=20 {
+            let mut game = Game::with_secret_in(range, secret);
+            assert_eq!(strategy::play(strategy.as_mut(), &mut game, &mut moves), Status::Won, "{name}");
+        }
+    }
+}
+
+#[test]
+fn binary_search_matches_the_optimal_bound() {
+    let range = Difficulty::Hard.range::<u32>();
+    let mut binary = strategy::by_name("binary").unwrap();
+    let report = simulate(binary.as_mut(), range, None, 0, 500, 42);
+    assert_eq!(report.wins, 500);
+    assert!(report.p99 <= score::optimal_attempts(range.size()));
+    assert!(report.mean_score > 900.0);
+}
+
+#[test]
+fn attempt_limits_cost_weak_strategies() {
+    let range = Difficulty::Normal.range::<u32>();
+    let mut linear = strategy::by_name("linear").unwrap();
+    let report = simulate(linear.as_mut(), range, Some(7), 0, 1_000, 42);
+    assert!(report.win_rate() < 0.1);
+    assert!(report.median <= 7);
+}
+
+#[test]
+fn simulations_are_reproducible() {
+    let range = Difficulty::Normal.range::<u32>();
+    let mut a = strategy::by_name("human").unwrap();
+    let mut b = strategy::by_name("human").unwrap();
+    assert_eq!(simulate(a.as_mut(), range, None, 0, 200, 3), simulate(b.as_mut(), range, None, 0, 200, 3));
+}
+
+#[test]
+fn every_strategy_survives_a_lying_oracle() {
+    let range = Difficulty::Normal.range::<u32>();
+    for name in NAMES {
+        let mut strategy = strategy::by_name(name).unwrap();
+        let report = simulate(strategy.as_mut(), range, None, 2, 300, 5);
+        assert_eq!(report.wins, 300, "{name}");
+    }
+}
+
+#[test]
+fn tolerant_search_beats_binary_search_under_lies() {
+    let range = Difficulty::Hard.range::<u32>();
+    let mut tolerant = strategy::by_name("tolerant").unwrap();
+    let mut binary = strategy::by_name("binary").unwrap();
+    let tolerant = simulate(tolerant.as_mut(), range, None, 3, 500, 8);
+    let binary = simulate(binary.as_mut(), range, None, 3, 500, 8);
+    assert!(tolerant.p99 < binary.p99, "{tolerant} vs {binary}");
+
+    let mut honest = strategy::by_name("tolerant").unwrap();
+    let report = simulate(honest.as_mut(), range, None, 0, 500, 42);
+    assert!(report.p99 <= score::optimal_attempts(range.size()), "without lies it is binary search");
+}
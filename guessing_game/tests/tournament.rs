@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::thread;
+use std::time::Duration;
+use guessing_game::tournament::{Entrant, Format, Player, RosterError, Tournament};
+use guessing_game::{Game, GuessRange, Session};
+
+fn roster(names: &[&str]) -> Vec<Entrant> {
+    names.iter().map(|name| name.parse().unwrap()).collect()
+}
+
+fn tournament(names: &[&str], format: Format) -> Tournament {
+    Tournament::new(roster(names), format, GuessRange::default(), Some(10), 99).unwrap()
+}
+
+/// Finds the secret on the `attempts`-th guess (or never, for 0).
+fn played(tournament: &Tournament, attempts: u32) -> Game {
+    let pairing = tournament.next_match().unwrap();
+    finish(tournament.game(&pairing), attempts)
+}
+
+fn finish(mut game: Game, attempts: u32) -> Game {
+    let secret = game.secret();
+    let miss = if secret == 1 { 2 } else { 1 };
+    for _ in 1..attempts {
+        game.guess(miss);
+    }
+    if attempts > 0 {
+        game.guess(secret);
+    } else {
+        game.give_up();
+    }
+    game
+}
+
+#[test]
+fn parses_humans_and_bots() {
+    assert_eq!("Ann".parse::<Entrant>().unwrap().player, Player::Human);
+    let bot: Entrant = "bot:binary".parse().unwrap();
+    assert_eq!((bot.name.as_str(), bot.player), ("binary", Player::Bot("binary")));
+    assert!("bot:psychic".parse::<Entrant>().is_err());
+    let program: Entrant = "cmd:./solver --fast".parse().unwrap();
+    assert_eq!(program.player, Player::Program("./solver --fast".to_string()));
+    assert!("cmd: ".parse::<Entrant>().is_err());
+    assert!(" ".parse::<Entrant>().is_err());
+}
+
+#[test]
+fn rejects_bad_rosters() {
+    let range = GuessRange::default();
+    let err = Tournament::new(roster(&["Ann"]), Format::RoundRobin, range, None, 1).unwrap_err();
+    assert_eq!(err, RosterError::Size(1));
+    let err = Tournament::new(roster(&["Ann", "bot:binary", "Ann"]), Format::Knockout, range, None, 1).unwrap_err();
+    assert_eq!(err, RosterError::DuplicateName("Ann".to_string()));
+}
+
+#[test]
+fn round_robin_pairs_everyone_once_with_shared_secrets() {
+    let mut t = tournament(&["A", "B", "C", "D", "E"], Format::RoundRobin);
+    let mut met = HashSet::new();
+    let mut secrets = HashSet::new();
+    while let Some(pairing) = t.next_match() {
+        assert!(met.insert((pairing.home.min(pairing.away), pairing.home.max(pairing.away))));
+        assert_eq!(t.game(&pairing).secret(), t.game(&pairing).secret());
+        secrets.insert(t.game(&pairing).secret());
+        let (home, away) = (played(&t, 3), played(&t, 3));
+        t.record(&pairing, &home, &away);
+    }
+    assert_eq!(met.len(), 10);
+    assert!(secrets.len() > 1, "matches should not all share one secret");
+    assert!(t.standings().iter().all(|s| (s.played, s.draws, s.points) == (4, 4, 4)));
+}
+
+#[test]
+fn standings_break_ties_on_attempts_in_won_games() {
+    let mut t = tournament(&["A", "B", "C"], Format::RoundRobin);
+    // Every match: the home side finds it, in 2 the first time and in 4
+    // after that; the away side gives up without a guess.
+    let mut fastest = None;
+    while let Some(pairing) = t.next_match() {
+        let attempts = if fastest.is_none() { 2 } else { 4 };
+        fastest.get_or_insert(pairing.home);
+        let (home, away) = (played(&t, attempts), played(&t, 0));
+        assert_eq!(t.record(&pairing, &home, &away).winner, Some(pairing.home));
+    }
+    let standings = t.standings();
+    assert!(standings.iter().all(|s| s.points == 2));
+    let attempts: Vec<u32> = standings.iter().map(|s| s.attempts).collect();
+    assert_eq!(attempts, [2, 4, 4]);
+    assert_eq!(t.champion(), fastest);
+}
+
+#[test]
+fn giving_up_early_gains_nothing() {
+    let mut t = tournament(&["Ann", "Bob"], Format::RoundRobin);
+    let pairing = t.next_match().unwrap();
+    let mut lost = t.game(&pairing).with_max_attempts(Some(2));
+    let miss = if lost.secret() == 1 { 2 } else { 1 };
+    lost.guess(miss);
+    lost.guess(miss);
+    let gave_up = played(&t, 0);
+    assert_eq!(t.record(&pairing, &lost, &gave_up).winner, None);
+
+    assert!(t.standings().iter().all(|s| (s.points, s.attempts) == (1, 0)));
+    assert_eq!(t.champion(), None);
+    assert_eq!(t.leaders(), [0, 1]);
+}
+
+#[test]
+fn time_breaks_ties_only_between_timed_players() {
+    let mut t = tournament(&["Ann", "bot:binary", "Bob"], Format::RoundRobin);
+    let pairing = t.next_match().unwrap();
+    let timed = |after: Duration| {
+        let mut game = t.game(&pairing);
+        game.start_clock();
+        thread::sleep(after);
+        finish(game, 3)
+    };
+    let (slow, quick, untimed) = (timed(Duration::from_millis(20)), timed(Duration::ZERO), played(&t, 3));
+    assert_eq!(t.record(&pairing, &slow, &untimed).winner, None);
+    assert_eq!(t.record(&pairing, &slow, &quick).winner, Some(pairing.away));
+}
+
+#[test]
+fn knockout_gives_byes_and_replays_draws() {
+    let mut t = tournament(&["Top", "Mid", "Low"], Format::Knockout);
+    // Top has a bye; Mid meets Low first.
+    let first = t.next_match().unwrap();
+    assert_eq!((first.round, first.home, first.away), (1, 1, 2));
+
+    let (home, away) = (played(&t, 4), played(&t, 4));
+    assert_eq!(t.record(&first, &home, &away).winner, None);
+    let replay = t.next_match().unwrap();
+    assert_eq!((replay.home, replay.away), (1, 2));
+    assert_ne!(replay.seed, first.seed);
+
+    let (home, away) = (played(&t, 0), played(&t, 6));
+    t.record(&replay, &home, &away);
+    let last = t.next_match().unwrap();
+    assert_eq!((last.round, last.home, last.away), (2, 0, 2));
+    let (home, away) = (played(&t, 3), played(&t, 2));
+    t.record(&last, &home, &away);
+
+    assert!(t.is_over());
+    assert_eq!(t.champion(), Some(2));
+    let order: Vec<String> = t.standings().into_iter().map(|s| s.name).collect();
+    assert_eq!(order, ["Low", "Top", "Mid"]);
+}
+
+#[test]
+fn knockout_draws_go_to_the_higher_seed_eventually() {
+    let mut t = tournament(&["A", "B"], Format::Knockout);
+    while let Some(pairing) = t.next_match() {
+        let (home, away) = (played(&t, 0), played(&t, 0));
+        t.record(&pairing, &home, &away);
+    }
+    assert_eq!(t.results().len(), 4);
+    assert_eq!(t.champion(), Some(0));
+}
+
+#[test]
+fn session_plays_humans_and_bots() {
+    let mut t = Tournament::new(roster(&["Ann", "bot:binary"]), Format::RoundRobin, GuessRange::default(), None, 3)
+        .unwrap();
+    let pairing = t.next_match().unwrap();
+    assert_eq!(t.game(&pairing).secret(), 99);
+    let mut session = Session::new(Cursor::new("99\n"), Vec::new());
+    session.play_tournament(&mut t).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+
+    assert!(output.starts_with("Round-robin tournament on 1-100, seed 3: Ann, binary (bot).\n"), "{output}");
+    assert!(output.contains("Ann, your turn.\nGuess the number (1-100)!\nYou win!\n"), "{output}");
+    assert!(output.contains("binary found it in 6 attempts.\nAnn wins the match.\n"), "{output}");
+    assert!(output.ends_with("Ann wins the tournament!\n"), "{output}");
+}
+
+#[test]
+fn humans_hand_off_the_keyboard_between_turns() {
+    let mut t = Tournament::new(roster(&["Ann", "Bob"]), Format::RoundRobin, GuessRange::default(), None, 3).unwrap();
+    let secret = t.game(&t.next_match().unwrap()).secret();
+    let mut session = Session::new(Cursor::new(format!("{secret}\n\n{secret}\n")), Vec::new());
+    session.play_tournament(&mut t).unwrap();
+    let output = String::from_utf8(session.into_inner().1).unwrap();
+
+    let (ann, bob) = output.split_once("\x1b[2J\x1b[H").expect("the screen is cleared before Bob's turn");
+    assert!(ann.contains("Ann, your turn.\nGuess the number (1-100)!\nYou win!\n"), "{output}");
+    assert!(ann.ends_with("Pass the keyboard to Bob and press Enter.\n"), "{output}");
+    assert!(bob.starts_with("Bob, your turn.\nGuess the number (1-100)!\nYou win!\n"), "{output}");
+    assert!(bob.contains("A draw."), "{output}");
+}
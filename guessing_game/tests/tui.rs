@@ -0,0 +1,43 @@
+use std::time::Duration;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use guessing_game::{tui, Game};
+
+fn screen(game: &Game, input: &str, message: &str) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(60, 16)).unwrap();
+    terminal.draw(|frame| tui::render(frame, game, input, message, Duration::from_secs(75))).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>() + "\n")
+        .collect()
+}
+
+#[test]
+fn shows_interval_history_and_counters() {
+    let mut game = Game::with_secret(30).with_max_attempts(Some(10));
+    game.guess(50);
+    game.guess(20);
+    let screen = screen(&game, "3", "20 is too low!");
+
+    assert!(screen.contains("Guess the number (1-100)"));
+    assert!(screen.contains("still possible: 21-49"));
+    assert!(screen.contains("1. 50") && screen.contains("▼ too high"));
+    assert!(screen.contains("2. 20") && screen.contains("▲ too low"));
+    assert!(screen.contains("Attempts: 2/10"));
+    assert!(screen.contains("Time: 01:15"));
+    assert!(screen.contains("> 3_"));
+    assert!(screen.contains("20 is too low!"));
+
+    let bar = screen.lines().nth(1).unwrap();
+    let lit = bar.chars().filter(|&c| c == '█').count();
+    assert!((15..=18).contains(&lit), "29 of 100 numbers on 58 cells, got {lit}");
+}
+
+#[test]
+fn finished_games_offer_exit() {
+    let mut game = Game::with_secret(7);
+    game.guess(7);
+    let screen = screen(&game, "", "You win!");
+    assert!(screen.contains("still possible: 7-7"));
+    assert!(screen.contains("Enter/Esc: exit"));
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use guessing_game::word::{self, Dictionary, Mark, WordError, WordGame};
+use guessing_game::Status;
+
+fn marks(secret: &str, guess: &str) -> String {
+    word::pattern(&word::mark(secret, guess))
+}
+
+#[test]
+fn marks_letters_like_wordle() {
+    assert_eq!(marks("crane", "crane"), "+++++");
+    assert_eq!(marks("crane", "slate"), "--+-+");
+    assert_eq!(marks("abbey", "babes"), "??++-");
+    assert_eq!(marks("abbey", "kebab"), "-?+??");
+    assert_eq!(marks("abbey", "bobby"), "?-+-+", "the b left over is present once");
+    assert_eq!(marks("speed", "eerie"), "??---", "only two e's can be marked");
+    assert_eq!(word::mark("ab", "ba"), [Mark::Present, Mark::Present]);
+}
+
+#[test]
+fn dictionaries_keep_one_length_of_plain_words() {
+    let dictionary = Dictionary::parse("# comment\nApple\nberry\nkiwi\nmango\nno-no\napple\n", 5).unwrap();
+    assert_eq!(dictionary.len(), 3);
+    assert!(dictionary.contains("apple") && !dictionary.contains("kiwi"));
+    assert!(Dictionary::parse("kiwi\n", 5).is_err());
+    assert!(Dictionary::builtin().len() > 500);
+
+    let path = std::env::temp_dir().join(format!("guessing_game_words_{}.txt", std::process::id()));
+    fs::write(&path, "kiwi\nlime\npear\n").unwrap();
+    assert_eq!(Dictionary::load(&path, 4).unwrap().len(), 3);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn guesses_must_be_known_words() {
+    let game = WordGame::with_secret(Dictionary::builtin(), "crane");
+    assert_eq!(game.parse(" SLATE\n"), Ok("slate".to_string()));
+    assert_eq!(game.parse(""), Err(WordError::Empty));
+    assert_eq!(game.parse("cran"), Err(WordError::WrongLength { expected: 5, found: 4 }));
+    assert_eq!(game.parse("cr4ne"), Err(WordError::NotLetters("cr4ne".into())));
+    assert_eq!(game.parse("qwxyz"), Err(WordError::NotAWord("qwxyz".into())));
+}
+
+#[test]
+fn hard_mode_enforces_revealed_letters() {
+    let mut game = WordGame::with_secret(Dictionary::builtin(), "crane").with_hard_mode(true);
+    game.guess("trace");  // -++?+
+    assert_eq!(game.parse("brave"), Err(WordError::MustKeep { letter: 'c', position: None }));
+    assert_eq!(game.parse("track").unwrap_err().to_string(), "hard mode: letter 5 must be E");
+    assert_eq!(game.parse("grace"), Ok("grace".to_string()));
+    assert_eq!(game.parse("crane"), Ok("crane".to_string()));
+}
+
+#[test]
+fn games_end_after_six_guesses() {
+    let mut game = WordGame::with_secret(Dictionary::builtin(), "crane");
+    for _ in 0..6 {
+        game.guess("slate");
+    }
+    assert_eq!(game.status(), Status::Lost);
+    assert_eq!(game.history().len(), 6);
+
+    let mut game = WordGame::with_secret(Dictionary::builtin(), "crane").with_max_attempts(None);
+    game.guess("crane");
+    assert_eq!(game.status(), Status::Won);
+}